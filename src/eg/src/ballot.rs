@@ -14,7 +14,7 @@ use util::{algebra::FieldElement, csprng::Csprng};
 use crate::{
     ballot_style::BallotStyleIndex,
     confirmation_code::confirmation_code,
-    contest_encrypted::{ContestEncrypted, ScaledContestEncrypted},
+    contest_encrypted::{ContestEncrypted, ContestVerifyError, ScaledContestEncrypted},
     contest_selection::ContestSelection,
     device::Device,
     election_manifest::{ContestIndex, ElectionManifest},
@@ -59,6 +59,7 @@ pub struct BallotEncrypted {
 
 /// Scaled version of [`BallotEncrypted`]. This means that each encrypted vote in the ballot
 /// has been scaled by factor. A [`ScaledBallotEncrypted`] does not contain any proofs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ScaledBallotEncrypted {
     /// Contests in this ballot
     pub contests: BTreeMap<ContestIndex, ScaledContestEncrypted>,
@@ -74,6 +75,32 @@ pub enum BallotEncryptedError {
     ContestNotInManifest { idx: ContestIndex },
 }
 
+/// Represents errors occurring while verifying a [`BallotEncrypted`].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum BallotVerifyError {
+    /// Occurs if the ballot style is not defined in the manifest.
+    #[error("Ballot style (index {idx}) not found in election manifest.")]
+    BallotStyleNotInManifest { idx: BallotStyleIndex },
+    /// Occurs if a contest is not defined in the manifest.
+    #[error("Contest (index {idx}) not found in election manifest.")]
+    ContestNotInManifest { idx: ContestIndex },
+    /// Occurs if a contest of the ballot style is missing from the ballot.
+    #[error("Contest (index {idx}) of the ballot style is missing from the ballot.")]
+    ContestMissing { idx: ContestIndex },
+    /// Occurs if the ballot contains a contest that is not part of its ballot style.
+    #[error("Contest (index {idx}) is not part of the ballot style.")]
+    ContestNotInBallotStyle { idx: ContestIndex },
+    /// Occurs if the proofs of a contest do not verify.
+    #[error("Contest (index {idx}) is invalid: {err}")]
+    ContestInvalid {
+        idx: ContestIndex,
+        err: ContestVerifyError,
+    },
+    /// Occurs if the confirmation code does not match the contest hashes.
+    #[error("The confirmation code does not match the contest hashes.")]
+    ConfirmationCodeMismatch,
+}
+
 impl BallotEncrypted {
     pub fn new(
         ballot_style_index: BallotStyleIndex,
@@ -146,25 +173,81 @@ impl BallotEncrypted {
     }
 
     /// Verify all of the [`ContestEncrypted`] in the [`BallotEncrypted`]. Given
-    /// a ballot style it checks that all contests are voted on in the
-    /// ballot style, and that all of the vote proofs are correct.
-    pub fn verify(&self, header: &PreVotingData) -> bool {
+    /// a ballot style it checks that exactly the contests of the ballot style are
+    /// voted on in the ballot and that all of the vote proofs are correct.
+    ///
+    /// The confirmation code is not checked, since how it is computed depends on how the
+    /// ballot was produced. See [`BallotEncrypted::verify_confirmation_code`].
+    pub fn verify(&self, header: &PreVotingData) -> Result<(), BallotVerifyError> {
         let Some(ballot_style) = header.manifest.ballot_styles.get(self.ballot_style_index) else {
-            return false;
+            return Err(BallotVerifyError::BallotStyleNotInManifest {
+                idx: self.ballot_style_index,
+            });
         };
         for contest_index in &ballot_style.contests {
-            let Some(contest) = header.manifest.contests.get(*contest_index) else {
-                return false;
+            let idx = *contest_index;
+            let Some(contest) = header.manifest.contests.get(idx) else {
+                return Err(BallotVerifyError::ContestNotInManifest { idx });
             };
             let Some(contest_encrypted) = self.contests().get(contest_index) else {
-                return false;
+                return Err(BallotVerifyError::ContestMissing { idx });
             };
 
-            if !contest_encrypted.verify(header, contest.selection_limit) {
-                return false;
-            }
+            contest_encrypted
+                .verify(header, contest.selection_limit)
+                .map_err(|err| BallotVerifyError::ContestInvalid { idx, err })?;
         }
-        true
+        if let Some(&idx) = self
+            .contests
+            .keys()
+            .find(|idx| !ballot_style.contests.contains(idx))
+        {
+            return Err(BallotVerifyError::ContestNotInBallotStyle { idx });
+        }
+
+        Ok(())
+    }
+
+    /// Verify that the confirmation code matches the contest hashes and the given
+    /// auxiliary data `b_aux` as in Equation 59.
+    ///
+    /// This only applies to ballots whose confirmation code was computed by Equation 59,
+    /// such as those produced by [`BallotEncrypted::new_from_selections`], which uses an
+    /// all-zero `b_aux` of 32 bytes. Pre-encrypted ballots use a different confirmation code.
+    pub fn verify_confirmation_code(
+        &self,
+        header: &PreVotingData,
+        b_aux: &[u8],
+    ) -> Result<(), BallotVerifyError> {
+        let confirmation_code =
+            confirmation_code(&header.hashes_ext.h_e, self.contests.values(), b_aux);
+        if confirmation_code != self.confirmation_code {
+            return Err(BallotVerifyError::ConfirmationCodeMismatch);
+        }
+        Ok(())
+    }
+
+    /// Verifies each [`ContestEncrypted`] in the [`BallotEncrypted`] on its own and reports
+    /// the outcome per contest, in the order of the contest indices.
+    ///
+    /// Unlike [`BallotEncrypted::verify`] this does not stop at the first invalid contest.
+    /// It also does not check the ballot style or the confirmation code.
+    pub fn verify_all_contests(
+        &self,
+        header: &PreVotingData,
+    ) -> Vec<(ContestIndex, Result<(), BallotVerifyError>)> {
+        self.contests
+            .iter()
+            .map(|(&idx, contest_encrypted)| {
+                let result = match header.manifest.contests.get(idx) {
+                    None => Err(BallotVerifyError::ContestNotInManifest { idx }),
+                    Some(contest) => contest_encrypted
+                        .verify(header, contest.selection_limit)
+                        .map_err(|err| BallotVerifyError::ContestInvalid { idx, err }),
+                };
+                (idx, result)
+            })
+            .collect()
     }
 
     /// Writes a `BallotEncrypted` to a `std::io::Write`.
@@ -270,14 +353,12 @@ mod test {
     use crate::{
         ballot::BallotEncrypted,
         ballot_style::BallotStyle,
+        contest_encrypted::test::{example_ballot, example_device, g_key},
         contest_selection::ContestSelection,
-        device::Device,
         election_manifest::{Contest, ContestOption},
         election_record::PreVotingData,
-        example_election_manifest::example_election_manifest,
         example_election_parameters::example_election_parameters,
         guardian_public_key::GuardianPublicKey,
-        guardian_secret_key::GuardianSecretKey,
         guardian_share::{GuardianEncryptedShare, GuardianSecretKeyShare},
         hashes::Hashes,
         hashes_ext::HashesExt,
@@ -288,120 +369,161 @@ mod test {
         },
     };
     use std::iter::zip;
-    use util::csprng::Csprng;
-
-    fn g_key(i: u32) -> GuardianSecretKey {
-        let mut seed = Vec::new();
-        let customization_data = format!("GuardianSecretKeyGenerate({})", i.clone());
-        seed.extend_from_slice(&(customization_data.as_bytes().len() as u64).to_be_bytes());
-        seed.extend_from_slice(customization_data.as_bytes());
-
-        let mut csprng = Csprng::new(&seed);
-
-        GuardianSecretKey::generate(
-            &mut csprng,
-            &example_election_parameters(),
-            Index::from_one_based_index_const(i).unwrap(),
-            None,
-        )
-    }
+    use util::{algebra::ScalarField, csprng::Csprng};
 
     #[test]
     fn test_verify_ballot() {
-        let election_manifest = example_election_manifest();
-        let election_parameters = example_election_parameters();
-
-        let sk1 = g_key(1);
-        let sk2 = g_key(2);
-        let sk3 = g_key(3);
-        let sk4 = g_key(4);
-        let sk5 = g_key(5);
+        let device = example_device();
+        let ballot_from_selections = example_ballot(&device);
 
-        let pk1 = sk1.make_public_key();
-        let pk2 = sk2.make_public_key();
-        let pk3 = sk3.make_public_key();
-        let pk4 = sk4.make_public_key();
-        let pk5 = sk5.make_public_key();
+        // Let's verify the ballot proofs.
 
-        let guardian_public_keys = vec![pk1, pk2, pk3, pk4, pk5];
+        let verify_result = ballot_from_selections.verify(&device.header);
 
-        let joint_election_public_key =
-            JointElectionPublicKey::compute(&election_parameters, guardian_public_keys.as_slice())
-                .unwrap();
+        assert_eq!(verify_result, Ok(()))
+    }
 
-        let hashes = Hashes::compute(&election_parameters, &election_manifest).unwrap();
+    #[test]
+    fn test_scale_ballot_by_one() {
+        let device = example_device();
+        let ballot = example_ballot(&device);
+        let fixed_parameters = &device.header.parameters.fixed_parameters;
+
+        let scaled = ballot.scale(fixed_parameters, &ScalarField::one());
+        assert_eq!(scaled.contests.len(), ballot.contests.len());
+        for (idx, contest) in &ballot.contests {
+            assert_eq!(scaled.contests[idx].selection, contest.selection);
+        }
 
-        let hashes_ext =
-            HashesExt::compute(&election_parameters, &hashes, &joint_election_public_key);
+        let json = serde_json::to_string(&scaled).unwrap();
+        let scaled_deserialized: ScaledBallotEncrypted = serde_json::from_str(&json).unwrap();
+        assert_eq!(scaled_deserialized, scaled);
+    }
 
-        let pre_voting_data = PreVotingData {
-            manifest: election_manifest,
-            parameters: election_parameters,
-            hashes,
-            hashes_ext,
-            public_key: joint_election_public_key,
-        };
-        let device = Device::new("Some encryption device", pre_voting_data);
-        let seed = vec![0, 1, 2, 3];
-        let mut csprng = Csprng::new(&seed);
-        let primary_nonce = vec![0, 1, 2, 2, 2, 2, 2, 2, 3];
-        let selections = BTreeMap::from([
-            (
-                Index::from_one_based_index(1).unwrap(),
-                ContestSelection::new(vec![1, 0]).unwrap(),
-            ),
-            (
-                Index::from_one_based_index(2).unwrap(),
-                ContestSelection::new(vec![0, 1, 0, 0]).unwrap(),
-            ),
-            (
-                Index::from_one_based_index(3).unwrap(),
-                ContestSelection::new(vec![0, 0, 1]).unwrap(),
-            ),
-            (
-                Index::from_one_based_index(4).unwrap(),
-                ContestSelection::new(vec![1, 0, 0]).unwrap(),
-            ),
+    #[test]
+    fn test_ballot_verify_error_display() {
+        let idx = ContestIndex::from_one_based_index(10).unwrap();
+        let cases = [
             (
-                Index::from_one_based_index(5).unwrap(),
-                ContestSelection::new(vec![0, 1, 1, 0, 0, 0, 0, 1, 0, 0, 0]).unwrap(),
+                BallotVerifyError::BallotStyleNotInManifest {
+                    idx: BallotStyleIndex::from_one_based_index(3).unwrap(),
+                },
+                "Ballot style (index 3) not found in election manifest.",
             ),
             (
-                Index::from_one_based_index(6).unwrap(),
-                ContestSelection::new(vec![1, 0]).unwrap(),
+                BallotVerifyError::ContestNotInManifest { idx },
+                "Contest (index 10) not found in election manifest.",
             ),
             (
-                Index::from_one_based_index(7).unwrap(),
-                ContestSelection::new(vec![0, 0]).unwrap(),
+                BallotVerifyError::ContestMissing { idx },
+                "Contest (index 10) of the ballot style is missing from the ballot.",
             ),
             (
-                Index::from_one_based_index(8).unwrap(),
-                ContestSelection::new(vec![0, 1]).unwrap(),
+                BallotVerifyError::ContestNotInBallotStyle { idx },
+                "Contest (index 10) is not part of the ballot style.",
             ),
             (
-                Index::from_one_based_index(9).unwrap(),
-                ContestSelection::new(vec![1, 0]).unwrap(),
+                BallotVerifyError::ContestInvalid {
+                    idx,
+                    err: ContestVerifyError::SelectionLimit { selection_limit: 1 },
+                },
+                "Contest (index 10) is invalid: The proof of satisfying the selection limit 1 is invalid.",
             ),
             (
-                Index::from_one_based_index(11).unwrap(),
-                ContestSelection::new(vec![0, 1]).unwrap(),
+                BallotVerifyError::ConfirmationCodeMismatch,
+                "The confirmation code does not match the contest hashes.",
             ),
-        ]);
+        ];
+        for (err, expected) in cases {
+            assert_eq!(err.to_string(), expected);
+        }
+    }
 
-        let ballot_from_selections = BallotEncrypted::new_from_selections(
-            Index::from_one_based_index(2).unwrap(),
-            &device,
-            &mut csprng,
-            &primary_nonce,
-            &selections,
-        )
-        .unwrap();
+    #[test]
+    fn test_verify_ballot_tampered() {
+        let device = example_device();
+        let c_idx = Index::from_one_based_index(1).unwrap();
 
-        // Let's verify the ballot proofs.
+        // Swapping two encrypted votes invalidates their proofs of ballot correctness.
+        let mut ballot = example_ballot(&device);
+        ballot
+            .contests
+            .get_mut(&c_idx)
+            .unwrap()
+            .selection
+            .swap(0, 1);
+        assert_eq!(
+            ballot.verify(&device.header),
+            Err(BallotVerifyError::ContestInvalid {
+                idx: c_idx,
+                err: ContestVerifyError::BallotCorrectness {
+                    idx: Index::from_one_based_index(1).unwrap()
+                },
+            })
+        );
 
-        let verify_result = ballot_from_selections.verify(&device.header);
+        // Removing a contest of the ballot style.
+        let mut ballot = example_ballot(&device);
+        ballot.contests.remove(&c_idx);
+        assert_eq!(
+            ballot.verify(&device.header),
+            Err(BallotVerifyError::ContestMissing { idx: c_idx })
+        );
 
-        assert!(verify_result)
+        // Changing the confirmation code.
+        let ballot = example_ballot(&device);
+        assert_eq!(
+            ballot.verify_confirmation_code(&device.header, &[0u8; 32]),
+            Ok(())
+        );
+        assert_eq!(
+            ballot.verify_confirmation_code(&device.header, &[1u8; 32]),
+            Err(BallotVerifyError::ConfirmationCodeMismatch)
+        );
+        let mut ballot = ballot;
+        ballot.confirmation_code = HValue::default();
+        assert_eq!(ballot.verify(&device.header), Ok(()));
+        assert_eq!(
+            ballot.verify_confirmation_code(&device.header, &[0u8; 32]),
+            Err(BallotVerifyError::ConfirmationCodeMismatch)
+        );
+    }
+
+    #[test]
+    fn test_verify_all_contests() {
+        let device = example_device();
+        let c_idx = Index::from_one_based_index(1).unwrap();
+
+        let ballot = example_ballot(&device);
+        let results = ballot.verify_all_contests(&device.header);
+        assert_eq!(results.len(), ballot.contests.len());
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+
+        // Tampering with one contest is reported for that contest only.
+        let mut ballot = example_ballot(&device);
+        ballot
+            .contests
+            .get_mut(&c_idx)
+            .unwrap()
+            .selection
+            .swap(0, 1);
+        let results = ballot.verify_all_contests(&device.header);
+        assert!(results.len() > 1);
+        for (idx, result) in results {
+            if idx == c_idx {
+                assert_eq!(
+                    result,
+                    Err(BallotVerifyError::ContestInvalid {
+                        idx: c_idx,
+                        err: ContestVerifyError::BallotCorrectness {
+                            idx: Index::from_one_based_index(1).unwrap()
+                        },
+                    })
+                );
+            } else {
+                assert_eq!(result, Ok(()));
+            }
+        }
     }
 
     fn short_manifest() -> ElectionManifest {
@@ -657,7 +779,7 @@ mod test {
         )
         .unwrap();
         let verify_result1 = ballot_voter1.verify(&device.header);
-        assert!(verify_result1);
+        assert!(verify_result1.is_ok());
         let ballot_voter2 = BallotEncrypted::new_from_selections(
             Index::from_one_based_index(2).unwrap(),
             &device,
@@ -667,7 +789,7 @@ mod test {
         )
         .unwrap();
         let verify_result2 = ballot_voter2.verify(&device.header);
-        assert!(verify_result2);
+        assert!(verify_result2.is_ok());
         let ballot_voter3 = BallotEncrypted::new_from_selections(
             Index::from_one_based_index(3).unwrap(),
             &device,
@@ -677,7 +799,7 @@ mod test {
         )
         .unwrap();
         let verify_result3 = ballot_voter3.verify(&device.header);
-        assert!(verify_result3);
+        assert!(verify_result3.is_ok());
 
         let factor = FieldElement::from(1u8, &fixed_parameters.field);
         let encrypted_ballots = vec![
@@ -18,13 +18,58 @@ pub fn confirmation_code<'a>(
     h_e: &HValue,
     contests: impl Iterator<Item = &'a ContestEncrypted>,
     b_aux: &[u8],
+) -> HValue {
+    confirmation_code_from_hashes(h_e, contests.map(|c| &c.contest_hash), b_aux)
+}
+
+/// Confirmation code for an encrypted ballot (Equation 59) computed directly from the
+/// contest hashes `χ_1,χ_2,...,χ_{m_B}`, given in the order of the contests on the ballot.
+///
+/// H(B) = H(H_E;24,χ_1,χ_2,...,χ_{m_B} ,B_aux).
+///
+pub fn confirmation_code_from_hashes<'a>(
+    h_e: &HValue,
+    contest_hashes: impl IntoIterator<Item = &'a HValue>,
+    b_aux: &[u8],
 ) -> HValue {
     let mut v = vec![0x24];
 
-    for item in contests {
-        v.extend(item.contest_hash.as_ref());
+    for contest_hash in contest_hashes {
+        v.extend(contest_hash.as_ref());
     }
 
     v.extend_from_slice(b_aux);
     eg_h(h_e, &v)
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_confirmation_code_from_hashes() {
+        let h_e = HValue::from([0xE0; 32]);
+        let contest_hashes = [HValue::from([1; 32]), HValue::from([2; 32])];
+        let b_aux = [0u8; 32];
+
+        let code = confirmation_code_from_hashes(&h_e, &contest_hashes, &b_aux);
+        assert_eq!(
+            code,
+            confirmation_code_from_hashes(&h_e, &contest_hashes, &b_aux)
+        );
+
+        let mut v = vec![0x24];
+        v.extend_from_slice(&[1; 32]);
+        v.extend_from_slice(&[2; 32]);
+        v.extend_from_slice(&b_aux);
+        assert_eq!(code, eg_h(&h_e, &v));
+
+        // The order of the contests matters.
+        let contest_hashes_reversed = [contest_hashes[1], contest_hashes[0]];
+        assert_ne!(
+            code,
+            confirmation_code_from_hashes(&h_e, &contest_hashes_reversed, &b_aux)
+        );
+    }
+}
@@ -6,11 +6,12 @@
 #![deny(clippy::manual_assert)]
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use util::{algebra::FieldElement, csprng::Csprng};
 
 use crate::{
     contest_hash,
-    contest_selection::ContestSelection,
+    contest_selection::{ContestSelection, VoteCount},
     device::Device,
     election_manifest::{Contest, ContestIndex, ContestOptionIndex},
     election_record::PreVotingData,
@@ -18,7 +19,7 @@ use crate::{
     hash::HValue,
     index::Index,
     joint_election_public_key::{Ciphertext, Nonce},
-    nonce::encrypted as nonce,
+    nonce,
     vec1::Vec1,
     zk::{ProofRange, ProofRangeError},
 };
@@ -42,11 +43,26 @@ pub struct ContestEncrypted {
     pub proof_selection_limit: ProofRange,
 }
 
+/// Represents errors occurring while verifying a [`ContestEncrypted`].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ContestVerifyError {
+    /// Occurs if the number of proofs of ballot correctness differs from the number of
+    /// encrypted selections.
+    #[error("Expected {expected} proofs of ballot correctness, found {found}.")]
+    ProofCountMismatch { expected: usize, found: usize },
+    /// Occurs if the proof that an encrypted vote is an encryption of 0 or 1 does not verify.
+    #[error("The proof of ballot correctness for option {idx} is invalid.")]
+    BallotCorrectness { idx: ContestOptionIndex },
+    /// Occurs if the proof of satisfying the selection limit does not verify.
+    #[error("The proof of satisfying the selection limit {selection_limit} is invalid.")]
+    SelectionLimit { selection_limit: usize },
+}
+
 /// A scaled version of [`ContestEncrypted`]. This means that each encrypted vote on the contest
 /// has been scaled by a factor. It is trusted that the encrypted ciphertexts in a
 /// [`ScaledContestEncrypted`] really are the ones from a [`ContestEncrypted`] scaled by a factor.
 /// Contains no proofs.
-#[derive(PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ScaledContestEncrypted {
     /// Scaled encrypted voter selection vector.
     pub selection: Vec<Ciphertext>,
@@ -74,21 +90,25 @@ impl ContestEncrypted {
     ) -> Vec<(Ciphertext, Nonce)> {
         // TODO: Check if selection limit is satisfied
 
-        let mut vote: Vec<(Ciphertext, Nonce)> = Vec::new();
-        for j in 1..=pt_vote.get_vote().len() {
-            // This is fine since 1 <= j <= Index::VALID_MAX_U32
-            let o_idx = ContestOptionIndex::from_one_based_index_unchecked(j as u32);
-            let nonce = nonce(header, primary_nonce, contest_index, o_idx);
-            vote.push((
-                header.public_key.encrypt_with(
+        let nonces = nonce::derive_contest_nonces(
+            header,
+            primary_nonce,
+            contest_index,
+            pt_vote.get_vote().len(),
+        );
+        pt_vote
+            .get_vote()
+            .iter()
+            .zip(nonces)
+            .map(|(&vote, nonce)| {
+                let ct = header.public_key.encrypt_with(
                     &header.parameters.fixed_parameters,
-                    &nonce,
-                    pt_vote.get_vote()[j - 1] as usize,
-                ),
-                Nonce::new(nonce),
-            ));
-        }
-        vote
+                    &nonce.xi,
+                    vote as usize,
+                );
+                (ct, nonce)
+            })
+            .collect()
     }
 
     pub fn new(
@@ -120,13 +140,11 @@ impl ContestEncrypted {
             )?);
         }
 
-        let mut num_selections = 0;
-        pt_vote.get_vote().iter().for_each(|v| num_selections += v);
         let proof_selection_limit = ContestEncrypted::proof_selection_limit(
             &device.header,
             csprng,
             &selection_and_nonce,
-            num_selections as usize,
+            pt_vote.num_selections(),
             contest.selection_limit,
         )?;
         Ok(ContestEncrypted {
@@ -141,15 +159,25 @@ impl ContestEncrypted {
         &self.proof_ballot_correctness
     }
 
+    /// Iterates over the encrypted votes together with the 1-based index of their contest option.
+    ///
+    /// Stops after [`ContestOptionIndex::VALID_MAX_U32`] items.
+    pub fn iter_selections(&self) -> impl Iterator<Item = (ContestOptionIndex, &Ciphertext)> {
+        ContestOptionIndex::iter_range_inclusive(ContestOptionIndex::MIN, ContestOptionIndex::MAX)
+            .zip(self.selection.iter())
+    }
+
     pub fn get_proof_selection_limit(&self) -> &ProofRange {
         &self.proof_selection_limit
     }
 
+    /// Proves that the total number of votes `num_selections` in the encrypted `selection`
+    /// does not exceed `selection_limit`.
     pub fn proof_selection_limit(
         header: &PreVotingData,
         csprng: &mut Csprng,
         selection: &[(Ciphertext, Nonce)],
-        num_selections: usize,
+        num_selections: VoteCount,
         selection_limit: usize,
     ) -> Result<ProofRange, ProofRangeError> {
         let (combined_ct, combined_nonce) =
@@ -159,7 +187,7 @@ impl ContestEncrypted {
             csprng,
             &combined_ct,
             &combined_nonce,
-            num_selections,
+            num_selections.as_usize(),
             selection_limit,
         )
     }
@@ -217,20 +245,104 @@ impl ContestEncrypted {
 
     /// Verify the proof that each encrypted vote is an encryption of 0 or 1,
     /// and that the selection limit is satisfied.
-    pub fn verify(&self, header: &PreVotingData, selection_limit: usize) -> bool {
-        for (ct, j) in self.selection.iter().zip(1..) {
-            let Ok(idx) = Index::from_one_based_index(j) else {
-                return false;
-            };
-            let Some(proof) = self.proof_ballot_correctness.get(idx) else {
-                return false;
-            };
+    pub fn verify(
+        &self,
+        header: &PreVotingData,
+        selection_limit: usize,
+    ) -> Result<(), ContestVerifyError> {
+        if self.selection.len() != self.proof_ballot_correctness.len() {
+            return Err(ContestVerifyError::ProofCountMismatch {
+                expected: self.selection.len(),
+                found: self.proof_ballot_correctness.len(),
+            });
+        }
+
+        for ((idx, ct), proof) in self
+            .iter_selections()
+            .zip(self.proof_ballot_correctness.iter())
+        {
             if !ct.verify_ballot_correctness(header, proof) {
-                return false;
+                return Err(ContestVerifyError::BallotCorrectness { idx });
             }
         }
 
-        self.verify_selection_limit(header, selection_limit)
+        if !self.verify_selection_limit(header, selection_limit) {
+            return Err(ContestVerifyError::SelectionLimit { selection_limit });
+        }
+
+        Ok(())
+    }
+
+    /// Returns a canonical byte encoding of the [`ContestEncrypted`] that does not depend on a
+    /// serde backend. This is intended for hashing and auditing.
+    ///
+    /// The fields are encoded in the following order, where counts are 4-byte big-endian integers
+    /// and group elements are left-padded to the length of `p`:
+    /// - the number of selections followed by `alpha | beta` of each selection ciphertext
+    /// - the 32 bytes of the contest hash
+    /// - the number of proofs of ballot correctness followed by each proof
+    /// - the proof of satisfying the selection limit
+    ///
+    /// The proofs are encoded with [`ProofRange::to_canonical_bytes`].
+    pub fn to_canonical_bytes(&self, fixed_parameters: &FixedParameters) -> Vec<u8> {
+        let field = &fixed_parameters.field;
+        let group = &fixed_parameters.group;
+
+        let mut v = Vec::new();
+        v.extend_from_slice(&(self.selection.len() as u32).to_be_bytes());
+        for ct in &self.selection {
+            v.extend_from_slice(ct.alpha.to_be_bytes_left_pad(group).as_slice());
+            v.extend_from_slice(ct.beta.to_be_bytes_left_pad(group).as_slice());
+        }
+        v.extend_from_slice(self.contest_hash.as_ref());
+        v.extend_from_slice(&(self.proof_ballot_correctness.len() as u32).to_be_bytes());
+        for proof in self.proof_ballot_correctness.iter() {
+            v.extend_from_slice(proof.to_canonical_bytes(field).as_slice());
+        }
+        v.extend_from_slice(
+            self.proof_selection_limit
+                .to_canonical_bytes(field)
+                .as_slice(),
+        );
+        v
+    }
+
+    /// Splits the [`ContestEncrypted`] into the encrypted selections, the contest hash,
+    /// the proofs of ballot correctness, and the proof of satisfying the selection limit.
+    ///
+    /// This allows storing the ciphertexts separately from the proofs.
+    pub fn into_parts(self) -> (Vec<Ciphertext>, HValue, Vec1<ProofRange>, ProofRange) {
+        (
+            self.selection,
+            self.contest_hash,
+            self.proof_ballot_correctness,
+            self.proof_selection_limit,
+        )
+    }
+
+    /// Reassembles a [`ContestEncrypted`] from the parts returned by [`ContestEncrypted::into_parts`].
+    ///
+    /// Fails if the number of proofs of ballot correctness differs from the number of
+    /// encrypted selections. The proofs themselves are not verified.
+    pub fn from_parts(
+        selection: Vec<Ciphertext>,
+        contest_hash: HValue,
+        proof_ballot_correctness: Vec1<ProofRange>,
+        proof_selection_limit: ProofRange,
+    ) -> Result<Self, ContestVerifyError> {
+        if selection.len() != proof_ballot_correctness.len() {
+            return Err(ContestVerifyError::ProofCountMismatch {
+                expected: selection.len(),
+                found: proof_ballot_correctness.len(),
+            });
+        }
+
+        Ok(ContestEncrypted {
+            selection,
+            contest_hash,
+            proof_ballot_correctness,
+            proof_selection_limit,
+        })
     }
 
     /// Scales all the encrypted votes on the contest by the same factor.
@@ -247,3 +359,278 @@ impl ContestEncrypted {
         ScaledContestEncrypted { selection }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod test {
+    use super::*;
+    use crate::{
+        ballot::BallotEncrypted, example_election_manifest::example_election_manifest,
+        example_election_parameters::example_election_parameters,
+        guardian_secret_key::GuardianSecretKey,
+    };
+    use std::collections::BTreeMap;
+
+    pub fn g_key(i: u32) -> GuardianSecretKey {
+        let mut seed = Vec::new();
+        let customization_data = format!("GuardianSecretKeyGenerate({})", i.clone());
+        seed.extend_from_slice(&(customization_data.as_bytes().len() as u64).to_be_bytes());
+        seed.extend_from_slice(customization_data.as_bytes());
+
+        let mut csprng = Csprng::new(&seed);
+
+        GuardianSecretKey::generate(
+            &mut csprng,
+            &example_election_parameters(),
+            Index::from_one_based_index_const(i).unwrap(),
+            None,
+        )
+    }
+
+    pub fn example_device() -> Device {
+        let election_manifest = example_election_manifest();
+        let election_parameters = example_election_parameters();
+
+        let guardian_public_keys: Vec<_> = (1..=5).map(|i| g_key(i).make_public_key()).collect();
+
+        let pre_voting_data = PreVotingData::compute(
+            election_manifest,
+            election_parameters,
+            &guardian_public_keys,
+        )
+        .unwrap();
+        Device::new("Some encryption device", pre_voting_data)
+    }
+
+    pub fn example_ballot(device: &Device) -> BallotEncrypted {
+        let seed = vec![0, 1, 2, 3];
+        let mut csprng = Csprng::new(&seed);
+        let primary_nonce = vec![0, 1, 2, 2, 2, 2, 2, 2, 3];
+        let selections = BTreeMap::from([
+            (
+                Index::from_one_based_index(1).unwrap(),
+                ContestSelection::new(vec![1, 0]).unwrap(),
+            ),
+            (
+                Index::from_one_based_index(2).unwrap(),
+                ContestSelection::new(vec![0, 1, 0, 0]).unwrap(),
+            ),
+            (
+                Index::from_one_based_index(3).unwrap(),
+                ContestSelection::new(vec![0, 0, 1]).unwrap(),
+            ),
+            (
+                Index::from_one_based_index(4).unwrap(),
+                ContestSelection::new(vec![1, 0, 0]).unwrap(),
+            ),
+            (
+                Index::from_one_based_index(5).unwrap(),
+                ContestSelection::new(vec![0, 1, 1, 0, 0, 0, 0, 1, 0, 0, 0]).unwrap(),
+            ),
+            (
+                Index::from_one_based_index(6).unwrap(),
+                ContestSelection::new(vec![1, 0]).unwrap(),
+            ),
+            (
+                Index::from_one_based_index(7).unwrap(),
+                ContestSelection::new(vec![0, 0]).unwrap(),
+            ),
+            (
+                Index::from_one_based_index(8).unwrap(),
+                ContestSelection::new(vec![0, 1]).unwrap(),
+            ),
+            (
+                Index::from_one_based_index(9).unwrap(),
+                ContestSelection::new(vec![1, 0]).unwrap(),
+            ),
+            (
+                Index::from_one_based_index(11).unwrap(),
+                ContestSelection::new(vec![0, 1]).unwrap(),
+            ),
+        ]);
+
+        BallotEncrypted::new_from_selections(
+            Index::from_one_based_index(2).unwrap(),
+            device,
+            &mut csprng,
+            &primary_nonce,
+            &selections,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_contest_verify_error_display() {
+        assert_eq!(
+            ContestVerifyError::ProofCountMismatch {
+                expected: 3,
+                found: 2
+            }
+            .to_string(),
+            "Expected 3 proofs of ballot correctness, found 2."
+        );
+        assert_eq!(
+            ContestVerifyError::BallotCorrectness {
+                idx: ContestOptionIndex::from_one_based_index(2).unwrap()
+            }
+            .to_string(),
+            "The proof of ballot correctness for option 2 is invalid."
+        );
+        assert_eq!(
+            ContestVerifyError::SelectionLimit { selection_limit: 1 }.to_string(),
+            "The proof of satisfying the selection limit 1 is invalid."
+        );
+    }
+
+    #[test]
+    fn test_contest_canonical_bytes() {
+        let device = example_device();
+        let ballot = example_ballot(&device);
+        let fixed_parameters = &device.header.parameters.fixed_parameters;
+
+        let contest = &ballot.contests[&Index::from_one_based_index(1).unwrap()];
+        let bytes = contest.to_canonical_bytes(fixed_parameters);
+        assert_eq!(bytes, contest.to_canonical_bytes(fixed_parameters));
+
+        // The encoding survives a serde round trip.
+        let json = serde_json::to_string(contest).unwrap();
+        let contest_deserialized: ContestEncrypted = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            contest_deserialized.to_canonical_bytes(fixed_parameters),
+            bytes
+        );
+
+        let mut contest_modified = contest.clone();
+        contest_modified.contest_hash = HValue::default();
+        assert_ne!(contest_modified.to_canonical_bytes(fixed_parameters), bytes);
+
+        let mut contest_modified = contest.clone();
+        contest_modified.selection.swap(0, 1);
+        assert_ne!(contest_modified.to_canonical_bytes(fixed_parameters), bytes);
+    }
+
+    #[test]
+    fn test_iter_selections() {
+        let device = example_device();
+        let ballot = example_ballot(&device);
+
+        // Contest 5 has 11 options.
+        let contest = &ballot.contests[&Index::from_one_based_index(5).unwrap()];
+        let selections: Vec<_> = contest.iter_selections().collect();
+        assert_eq!(selections.len(), 11);
+        for (i, (idx, ct)) in selections.into_iter().enumerate() {
+            assert_eq!(idx.get_one_based_usize(), i + 1);
+            assert_eq!(ct, &contest.selection[i]);
+        }
+    }
+
+    #[test]
+    fn test_contest_parts_round_trip() {
+        let device = example_device();
+        let ballot = example_ballot(&device);
+        let fixed_parameters = &device.header.parameters.fixed_parameters;
+
+        let contest_ix = Index::from_one_based_index(1).unwrap();
+        let contest = &ballot.contests[&contest_ix];
+        let selection_limit = device
+            .header
+            .manifest
+            .contests
+            .get(contest_ix)
+            .unwrap()
+            .selection_limit;
+        let (selection, contest_hash, proof_ballot_correctness, proof_selection_limit) =
+            contest.clone().into_parts();
+        assert_eq!(selection.len(), proof_ballot_correctness.len());
+
+        let contest_reassembled = ContestEncrypted::from_parts(
+            selection.clone(),
+            contest_hash,
+            proof_ballot_correctness.clone(),
+            proof_selection_limit.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            contest_reassembled.to_canonical_bytes(fixed_parameters),
+            contest.to_canonical_bytes(fixed_parameters)
+        );
+        assert!(contest_reassembled
+            .verify(&device.header, selection_limit)
+            .is_ok());
+
+        // The number of proofs has to match the number of selections.
+        let mut selection_short = selection;
+        selection_short.pop();
+        assert_eq!(
+            ContestEncrypted::from_parts(
+                selection_short,
+                contest_hash,
+                proof_ballot_correctness,
+                proof_selection_limit,
+            )
+            .unwrap_err(),
+            ContestVerifyError::ProofCountMismatch {
+                expected: contest.selection.len() - 1,
+                found: contest.selection.len(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_proof_selection_limit_vote_count() {
+        let device = example_device();
+        let header = &device.header;
+        let fixed_parameters = &header.parameters.fixed_parameters;
+        let mut csprng = Csprng::new(b"test_proof_selection_limit_vote_count");
+
+        let votes = ContestSelection::new(vec![1, 0, 1]).unwrap();
+        let selection: Vec<_> = votes
+            .get_vote()
+            .iter()
+            .map(|&v| {
+                let nonce = Nonce::new(fixed_parameters.field.random_field_elem(&mut csprng));
+                let ct = header
+                    .public_key
+                    .encrypt_with(fixed_parameters, &nonce.xi, v as usize);
+                (ct, nonce)
+            })
+            .collect();
+        let cts: Vec<_> = selection.iter().map(|(ct, _)| ct.clone()).collect();
+        let combined_ct = ContestEncrypted::sum_selection_vector(fixed_parameters, &cts);
+
+        let proof = ContestEncrypted::proof_selection_limit(
+            header,
+            &mut csprng,
+            &selection,
+            votes.num_selections(),
+            2,
+        )
+        .unwrap();
+        assert!(proof.verify(header, &combined_ct, 2));
+
+        // Proving from a precomputed combined ciphertext gives a proof for the same statement.
+        let (combined_ct_2, combined_nonce) =
+            ContestEncrypted::sum_selection_nonce_vector(fixed_parameters, &selection);
+        assert_eq!(combined_ct_2, combined_ct);
+        let proof = ProofRange::new(
+            header,
+            &mut csprng,
+            &combined_ct,
+            &combined_nonce,
+            votes.num_selections().as_usize(),
+            2,
+        )
+        .unwrap();
+        assert!(proof.verify(header, &combined_ct, 2));
+
+        // A count above the limit cannot be proven.
+        let result = ContestEncrypted::proof_selection_limit(
+            header,
+            &mut csprng,
+            &selection,
+            VoteCount::new(3),
+            2,
+        );
+        assert!(result.is_err());
+    }
+}
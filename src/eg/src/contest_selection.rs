@@ -6,6 +6,7 @@
 #![deny(clippy::manual_assert)]
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use util::csprng::Csprng;
 
@@ -20,6 +21,77 @@ use crate::{
 
 pub type ContestSelectionPlaintext = u8;
 
+/// A count of votes, e.g., the number of options selected in a contest.
+///
+/// This is kept separate from indices and raw integers so that the units are explicit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct VoteCount(u32);
+
+impl VoteCount {
+    /// The count of zero votes.
+    pub const ZERO: VoteCount = VoteCount(0);
+
+    /// The largest representable count.
+    pub const MAX: VoteCount = VoteCount(u32::MAX);
+
+    /// Creates a new `VoteCount`.
+    pub const fn new(count: u32) -> Self {
+        VoteCount(count)
+    }
+
+    /// Obtains the count as a `u32`.
+    pub const fn get(&self) -> u32 {
+        self.0
+    }
+
+    /// Obtains the count as a `usize`.
+    pub const fn as_usize(&self) -> usize {
+        self.0 as usize
+    }
+
+    /// Adds two counts, returning `None` on overflow.
+    pub const fn checked_add(self, other: VoteCount) -> Option<VoteCount> {
+        match self.0.checked_add(other.0) {
+            Some(count) => Some(VoteCount(count)),
+            None => None,
+        }
+    }
+
+    /// Subtracts a count, returning `None` if the result would be negative.
+    pub const fn checked_sub(self, other: VoteCount) -> Option<VoteCount> {
+        match self.0.checked_sub(other.0) {
+            Some(count) => Some(VoteCount(count)),
+            None => None,
+        }
+    }
+
+    /// Adds two counts, saturating at [`VoteCount::MAX`].
+    pub const fn saturating_add(self, other: VoteCount) -> VoteCount {
+        VoteCount(self.0.saturating_add(other.0))
+    }
+
+    /// Sums up the plaintext votes, saturating at [`VoteCount::MAX`].
+    pub fn sum_of(votes: &[ContestSelectionPlaintext]) -> VoteCount {
+        votes.iter().fold(VoteCount::ZERO, |acc, &v| {
+            acc.saturating_add(VoteCount(v as u32))
+        })
+    }
+}
+
+impl From<u32> for VoteCount {
+    #[inline]
+    fn from(count: u32) -> Self {
+        VoteCount(count)
+    }
+}
+
+impl std::fmt::Display for VoteCount {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// A 1-based index of a [`ContestSelection`].
 pub type ContestSelectionIndex = Index<ContestSelection>;
 
@@ -30,6 +102,30 @@ pub struct ContestSelection {
     vote: Vec<ContestSelectionPlaintext>,
 }
 
+/// Represents errors occurring while converting a [`ContestSelection`] to or from a bitset.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ContestSelectionBitsetError {
+    /// Occurs if a vote is neither 0 nor 1 and therefore cannot be stored as a single bit.
+    #[error("The vote {vote} at position {ix} is not 0 or 1.")]
+    NotBinary {
+        ix: usize,
+        vote: ContestSelectionPlaintext,
+    },
+    /// Occurs if the number of bytes does not match the number of options.
+    #[error("Expected {expected} bytes for {num_options} options, found {found}.")]
+    LengthMismatch {
+        num_options: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// Occurs if a bit beyond the last option is set.
+    #[error("The bitset has bits set beyond the {num_options} options.")]
+    PaddingNotZero { num_options: usize },
+    /// Occurs if the number of options exceeds the maximum size of a selection.
+    #[error("The number of options {num_options} is too large.")]
+    TooManyOptions { num_options: usize },
+}
+
 impl HasIndexType for ContestSelection {
     type IndexType = Contest;
 }
@@ -46,6 +142,59 @@ impl ContestSelection {
         &self.vote
     }
 
+    /// The total number of votes in the selection.
+    pub fn num_selections(&self) -> VoteCount {
+        VoteCount::sum_of(&self.vote)
+    }
+
+    /// Packs the selection into a bitset with one bit per option.
+    ///
+    /// The vote for the option at (0-based) position `ix` is stored in bit `ix % 8`
+    /// (least significant first) of byte `ix / 8`. Unused bits of the last byte are zero.
+    pub fn to_bitset(&self) -> Result<Vec<u8>, ContestSelectionBitsetError> {
+        let mut bits = vec![0u8; self.vote.len().div_ceil(8)];
+        for (ix, &vote) in self.vote.iter().enumerate() {
+            match vote {
+                0 => {}
+                1 => bits[ix / 8] |= 1 << (ix % 8),
+                _ => return Err(ContestSelectionBitsetError::NotBinary { ix, vote }),
+            }
+        }
+        Ok(bits)
+    }
+
+    /// Unpacks a selection of `num_options` votes from a bitset created by
+    /// [`ContestSelection::to_bitset`].
+    pub fn from_bitset(
+        bits: &[u8],
+        num_options: usize,
+    ) -> Result<ContestSelection, ContestSelectionBitsetError> {
+        if num_options > Index::<ContestSelectionPlaintext>::VALID_MAX_USIZE {
+            return Err(ContestSelectionBitsetError::TooManyOptions { num_options });
+        }
+
+        let expected = num_options.div_ceil(8);
+        if bits.len() != expected {
+            return Err(ContestSelectionBitsetError::LengthMismatch {
+                num_options,
+                expected,
+                found: bits.len(),
+            });
+        }
+
+        if let Some(&last) = bits.last() {
+            let cnt_used_bits = num_options - 8 * (expected - 1);
+            if cnt_used_bits < 8 && last >> cnt_used_bits != 0 {
+                return Err(ContestSelectionBitsetError::PaddingNotZero { num_options });
+            }
+        }
+
+        let vote = (0..num_options)
+            .map(|ix| (bits[ix / 8] >> (ix % 8)) & 1)
+            .collect();
+        Ok(ContestSelection { vote })
+    }
+
     pub fn new_pick_random(
         csprng: &mut Csprng,
         selection_limit: usize,
@@ -86,3 +235,95 @@ impl Ciphertext {
         proof.verify(header, self, 1)
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bitset_round_trip() {
+        for num_options in [0, 1, 7, 8, 9, 20] {
+            let mut csprng = Csprng::new(b"test_bitset_round_trip");
+            let selection =
+                ContestSelection::new_pick_random(&mut csprng, num_options, num_options);
+
+            let bits = selection.to_bitset().unwrap();
+            assert_eq!(bits.len(), num_options.div_ceil(8));
+
+            let selection2 = ContestSelection::from_bitset(&bits, num_options).unwrap();
+            assert_eq!(selection2.get_vote(), selection.get_vote());
+        }
+
+        let selection = ContestSelection::new(vec![1, 0, 0, 1, 1, 0, 0, 0, 0, 1]).unwrap();
+        assert_eq!(
+            selection.to_bitset().unwrap(),
+            vec![0b0001_1001, 0b0000_0010]
+        );
+
+        let selection = ContestSelection::new(vec![0, 2]).unwrap();
+        assert_eq!(
+            selection.to_bitset(),
+            Err(ContestSelectionBitsetError::NotBinary { ix: 1, vote: 2 })
+        );
+    }
+
+    #[test]
+    fn test_bitset_inconsistent_length() {
+        assert_eq!(
+            ContestSelection::from_bitset(&[0, 0], 8).unwrap_err(),
+            ContestSelectionBitsetError::LengthMismatch {
+                num_options: 8,
+                expected: 1,
+                found: 2
+            }
+        );
+        assert_eq!(
+            ContestSelection::from_bitset(&[0], 9).unwrap_err(),
+            ContestSelectionBitsetError::LengthMismatch {
+                num_options: 9,
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(
+            ContestSelection::from_bitset(&[0], 0).unwrap_err(),
+            ContestSelectionBitsetError::LengthMismatch {
+                num_options: 0,
+                expected: 0,
+                found: 1
+            }
+        );
+        assert_eq!(
+            ContestSelection::from_bitset(&[0b0000_1000], 3).unwrap_err(),
+            ContestSelectionBitsetError::PaddingNotZero { num_options: 3 }
+        );
+        assert!(ContestSelection::from_bitset(&[0b0000_0100], 3).is_ok());
+    }
+
+    #[test]
+    fn test_vote_count_arithmetic() {
+        let one = VoteCount::new(1);
+        let two = VoteCount::from(2);
+
+        assert_eq!(one.checked_add(two), Some(VoteCount::new(3)));
+        assert_eq!(VoteCount::MAX.checked_add(one), None);
+        assert_eq!(two.checked_sub(one), Some(one));
+        assert_eq!(one.checked_sub(two), None);
+        assert_eq!(VoteCount::MAX.saturating_add(two), VoteCount::MAX);
+        assert_eq!(VoteCount::new(3).as_usize(), 3);
+        assert_eq!(VoteCount::new(3).to_string(), "3");
+    }
+
+    #[test]
+    fn test_vote_count_sum_of() {
+        assert_eq!(VoteCount::sum_of(&[]), VoteCount::ZERO);
+        assert_eq!(VoteCount::sum_of(&[0, 1, 1, 0, 1]), VoteCount::new(3));
+
+        // Plaintext votes are `u8`s, so summing many of them must not wrap around.
+        assert_eq!(VoteCount::sum_of(&[255; 3]), VoteCount::new(765));
+
+        let selection = ContestSelection::new(vec![1, 0, 1]).unwrap();
+        assert_eq!(selection.num_selections(), VoteCount::new(2));
+    }
+}
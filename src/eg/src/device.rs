@@ -5,6 +5,9 @@
 #![deny(clippy::panic)]
 #![deny(clippy::manual_assert)]
 
+use anyhow::{Context, Result};
+use util::csprng::Csprng;
+
 use crate::election_record::PreVotingData;
 
 pub struct Device {
@@ -23,7 +26,56 @@ impl Device {
         }
     }
 
+    /// Creates a new device after verifying that the election parameters and the joint
+    /// election public key in the header are valid.
+    pub fn new_validated(uuid: &str, header: PreVotingData, csprng: &mut Csprng) -> Result<Self> {
+        header
+            .parameters
+            .validate(csprng)
+            .context("Invalid election parameters")?;
+        header
+            .public_key
+            .validate(&header.parameters)
+            .context("Invalid joint election public key")?;
+        Ok(Device::new(uuid, header))
+    }
+
     pub fn get_uuid(&self) -> &String {
         &self.uuid
     }
+
+    /// Returns the election record header.
+    pub fn header(&self) -> &PreVotingData {
+        &self.header
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::contest_encrypted::test::example_device;
+    use num_bigint::BigUint;
+    use util::algebra::{Group, ScalarField};
+
+    #[test]
+    fn test_new_validated() {
+        let mut csprng = Csprng::new(b"test_device_new_validated");
+        let header = example_device().header;
+
+        let device = Device::new_validated("Some device", header.clone(), &mut csprng).unwrap();
+        assert_eq!(device.get_uuid(), "Some device");
+        assert_eq!(device.header().public_key, header.public_key);
+
+        // The joint election public key must not be the identity.
+        let mut bad_key_header = header.clone();
+        bad_key_header.public_key.joint_election_public_key = Group::one();
+        assert!(Device::new_validated("Some device", bad_key_header, &mut csprng).is_err());
+
+        // The field order must be prime.
+        let mut bad_parameters_header = header;
+        bad_parameters_header.parameters.fixed_parameters.field =
+            ScalarField::new_unchecked(BigUint::from(125_u8));
+        assert!(Device::new_validated("Some device", bad_parameters_header, &mut csprng).is_err());
+    }
 }
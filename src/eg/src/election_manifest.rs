@@ -5,9 +5,9 @@
 #![deny(clippy::panic)]
 #![deny(clippy::manual_assert)]
 
-use std::io::Cursor;
+use std::{collections::BTreeSet, io::Cursor};
 
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use serde::{Deserialize, Serialize};
 
 use crate::ballot_style::BallotStyle;
@@ -46,7 +46,11 @@ impl ElectionManifest {
     /// Validates that the [`ElectionManifest`] is well-formed.
     /// Useful after deserialization.
     pub fn validate(&self) -> Result<()> {
-        // We currently have no validation rules for this type.
+        for (contest_ix, contest) in self.contests.indices().zip(self.contests.iter()) {
+            contest
+                .validate()
+                .with_context(|| format!("Contest {contest_ix} is invalid"))?;
+        }
         Ok(())
     }
 
@@ -90,6 +94,38 @@ pub struct Contest {
     pub options: Vec1<ContestOption>,
 }
 
+impl Contest {
+    /// Validates that the [`Contest`] is well-formed, i.e., that
+    /// - its label and the labels of all its options are non-empty,
+    /// - the labels of its options are unique, and
+    /// - the selection limit does not exceed the number of options.
+    pub fn validate(&self) -> Result<()> {
+        ensure!(!self.label.is_empty(), "Contest label is empty");
+
+        let mut labels = BTreeSet::new();
+        for (option_ix, option) in self.options.indices().zip(self.options.iter()) {
+            ensure!(
+                !option.label.is_empty(),
+                "Label of contest option {option_ix} is empty"
+            );
+            ensure!(
+                labels.insert(option.label.as_str()),
+                "Label of contest option {option_ix} is a duplicate: {:?}",
+                option.label
+            );
+        }
+
+        ensure!(
+            self.selection_limit <= self.options.len(),
+            "Selection limit {} exceeds the number of options {}",
+            self.selection_limit,
+            self.options.len()
+        );
+
+        Ok(())
+    }
+}
+
 impl HasIndexTypeMarker for Contest {}
 
 /// A 1-based index of a [`Contest`] in the order it is defined in the [`ElectionManifest`].
@@ -156,4 +192,47 @@ pub mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_election_manifest_validate() {
+        let election_manifest = example_election_manifest();
+        assert!(election_manifest.validate().is_ok());
+
+        let contest_ix = ContestIndex::from_one_based_index(2).unwrap();
+
+        // Duplicate option label
+        let mut manifest = election_manifest.clone();
+        let contest = manifest.contests.get_mut(contest_ix).unwrap();
+        let label = contest.options.iter().next().unwrap().label.clone();
+        contest.options.try_push(ContestOption { label }).unwrap();
+        let err = manifest.validate().unwrap_err();
+        assert_eq!(err.to_string(), "Contest 2 is invalid");
+        assert!(format!("{err:#}").contains("Label of contest option 5 is a duplicate"));
+
+        // Selection limit larger than the number of options
+        let mut manifest = election_manifest.clone();
+        let contest = manifest.contests.get_mut(contest_ix).unwrap();
+        contest.selection_limit = contest.options.len() + 1;
+        assert!(manifest.validate().is_err());
+
+        // Empty option label
+        let mut manifest = election_manifest.clone();
+        let contest = manifest.contests.get_mut(contest_ix).unwrap();
+        contest
+            .options
+            .try_push(ContestOption {
+                label: "".to_string(),
+            })
+            .unwrap();
+        assert!(manifest.validate().is_err());
+
+        // Deserialization rejects an invalid manifest.
+        let mut manifest = election_manifest;
+        let contest = manifest.contests.get_mut(contest_ix).unwrap();
+        contest.selection_limit = contest.options.len() + 1;
+        let canonical_bytes = manifest.to_canonical_bytes().unwrap();
+        assert!(
+            ElectionManifest::from_stdioread_validated(&mut Cursor::new(canonical_bytes)).is_err()
+        );
+    }
 }
@@ -133,3 +133,70 @@ impl FixedParameters {
         Ok(())
     }
 }
+
+#[cfg(test)]
+impl FixedParameters {
+    /// Small fixed parameters with which encryption and proofs can be tested quickly.
+    ///
+    /// These provide no security whatsoever and must never be used outside of tests.
+    pub fn insecure_small_for_tests() -> FixedParameters {
+        crate::standard_parameters::test_parameter_do_not_use_in_production::make_toy_parameters_1()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::{
+        election_parameters::ElectionParameters, election_record::PreVotingData,
+        example_election_manifest::example_election_manifest,
+        example_election_parameters::example_election_parameters,
+        guardian_secret_key::GuardianSecretKey, joint_election_public_key::Nonce, zk::ProofRange,
+    };
+
+    #[test]
+    fn test_insecure_small_for_tests() {
+        let mut csprng = Csprng::new(b"test_insecure_small_for_tests");
+        let fixed_parameters = FixedParameters::insecure_small_for_tests();
+        assert!(fixed_parameters.validate(&mut csprng).is_ok());
+
+        let election_parameters = ElectionParameters {
+            fixed_parameters,
+            varying_parameters: example_election_parameters().varying_parameters,
+        };
+        let guardian_public_keys: Vec<_> = election_parameters
+            .varying_parameters
+            .each_guardian_i()
+            .map(|i| {
+                GuardianSecretKey::generate(&mut csprng, &election_parameters, i, None)
+                    .make_public_key()
+            })
+            .collect();
+        let pvd = PreVotingData::compute(
+            example_election_manifest(),
+            election_parameters,
+            &guardian_public_keys,
+        )
+        .unwrap();
+        let fixed_parameters = &pvd.parameters.fixed_parameters;
+        let field = &fixed_parameters.field;
+
+        // Encrypt a selection of each possible value and verify the proof of ballot correctness.
+        let mut encrypted = Vec::new();
+        for vote in [0, 1] {
+            let nonce = Nonce::new(field.random_field_elem(&mut csprng));
+            let ct = pvd
+                .public_key
+                .encrypt_with(fixed_parameters, &nonce.xi, vote);
+            let proof = ProofRange::new(&pvd, &mut csprng, &ct, &nonce, vote, 1).unwrap();
+            assert!(ct.verify_ballot_correctness(&pvd, &proof));
+            encrypted.push((ct, proof));
+        }
+
+        // A proof does not verify for a different ciphertext.
+        assert!(!encrypted[0]
+            .0
+            .verify_ballot_correctness(&pvd, &encrypted[1].1));
+    }
+}
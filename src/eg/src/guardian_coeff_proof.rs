@@ -149,6 +149,32 @@ impl CoefficientProof {
     }
 }
 
+/// This function generates a guardian key pair `(a_i_0, K_i_0 = g^a_i_0)` together with a
+/// [`CoefficientProof`] of knowledge of the secret, i.e., the proof for coefficient index `j = 0`.
+///
+/// The secret is a uniformly random non-zero element of `Z_q`.
+///
+/// The arguments are
+/// - `csprng` - secure randomness generator
+/// - `fixed_parameters` - the fixed parameters
+/// - `i` - the guardian index
+pub fn generate_keypair_with_proof(
+    csprng: &mut Csprng,
+    fixed_parameters: &FixedParameters,
+    i: u32,
+) -> (SecretCoefficient, CoefficientCommitment, CoefficientProof) {
+    let field = &fixed_parameters.field;
+    let secret = loop {
+        let x = field.random_field_elem(csprng);
+        if !x.is_zero() {
+            break SecretCoefficient(x);
+        }
+    };
+    let public = CoefficientCommitment(fixed_parameters.group.g_exp(&secret.0));
+    let proof = CoefficientProof::new(csprng, fixed_parameters, i, 0, &secret, &public);
+    (secret, public, proof)
+}
+
 #[cfg(test)]
 mod test {
 
@@ -160,7 +186,7 @@ mod test {
         guardian_secret_key::{CoefficientCommitment, SecretCoefficient},
     };
 
-    use super::CoefficientProof;
+    use super::{generate_keypair_with_proof, CoefficientProof};
 
     fn setup(
         csprng: &mut Csprng,
@@ -228,4 +254,28 @@ mod test {
             "Proof should fail"
         );
     }
+
+    #[test]
+    fn test_generate_keypair_with_proof() {
+        let mut csprng = Csprng::new(b"test_generate_keypair_with_proof");
+        let fixed_parameters = example_election_parameters().fixed_parameters;
+        let group = &fixed_parameters.group;
+
+        let (secret, public, proof) =
+            generate_keypair_with_proof(&mut csprng, &fixed_parameters, 3);
+        assert!(!secret.0.is_zero());
+        assert_eq!(public.0, group.g_exp(&secret.0));
+        assert!(
+            proof.validate(&fixed_parameters, 3, 0, &public).is_ok(),
+            "Proof should be valid"
+        );
+
+        let tampered_public = CoefficientCommitment(public.0.mul(&group.generator(), group));
+        assert!(
+            proof
+                .validate(&fixed_parameters, 3, 0, &tampered_public)
+                .is_err(),
+            "Proof should fail"
+        );
+    }
 }
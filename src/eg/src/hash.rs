@@ -6,7 +6,11 @@ use digest::{FixedOutput, Update};
 use hmac::{Hmac, Mac};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use util::array_ascii::ArrayAscii;
+use util::{
+    algebra::{Group, GroupElement},
+    array_ascii::ArrayAscii,
+    bitwise::ct_eq_bytes,
+};
 
 type HmacSha256 = Hmac<sha2::Sha256>;
 
@@ -113,6 +117,23 @@ impl HValue {
             .context("Error writing HValue file")
     }
 
+    /// Returns a compact representation showing only the first and last four bytes,
+    /// e.g., `H(00010203..1C1D1E1F)`. Intended for logging many values.
+    pub fn to_short_debug(&self) -> String {
+        let hex = |bytes: &[u8]| -> String { bytes.iter().map(|b| format!("{b:02X}")).collect() };
+        format!(
+            "H({}..{})",
+            hex(&self.0[..4]),
+            hex(&self.0[HVALUE_BYTE_LEN - 4..])
+        )
+    }
+
+    /// Compares two hash values without short-circuiting on the first differing byte.
+    /// Use this instead of `==` when one of the values is secret.
+    pub fn ct_eq(&self, other: &HValue) -> bool {
+        ct_eq_bytes(&self.0, &other.0)
+    }
+
     pub fn to_string_hex_no_prefix_suffix(&self) -> String {
         #[allow(clippy::unwrap_used)] //? TODO: Remove temp development code
         let s = serde_json::to_string_pretty(self).unwrap();
@@ -134,6 +155,28 @@ impl From<&HValueByteArray> for HValue {
     }
 }
 
+impl From<HValue> for HValueByteArray {
+    #[inline]
+    fn from(value: HValue) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<&[u8]> for HValue {
+    type Error = anyhow::Error;
+
+    /// Converts a slice of exactly [`HVALUE_BYTE_LEN`] bytes into an HValue.
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let hvba: HValueByteArray = value.try_into().map_err(|_| {
+            anyhow!(
+                "Expected {HVALUE_BYTE_LEN} bytes for an HValue, got {}",
+                value.len()
+            )
+        })?;
+        Ok(HValue(hvba))
+    }
+}
+
 impl AsRef<HValueByteArray> for HValue {
     #[inline]
     fn as_ref(&self) -> &HValueByteArray {
@@ -155,12 +198,18 @@ impl std::fmt::Debug for HValue {
     }
 }
 
+/// Human-readable formats such as JSON use the `H(...)` string form.
+/// Binary formats use the 32 raw bytes.
 impl Serialize for HValue {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        self.display_as_ascii().as_str().serialize(serializer)
+        if serializer.is_human_readable() {
+            self.display_as_ascii().as_str().serialize(serializer)
+        } else {
+            self.0.serialize(serializer)
+        }
     }
 }
 
@@ -240,6 +289,10 @@ impl<'de> Deserialize<'de> for HValue {
     {
         use serde::de::Error;
 
+        if !deserializer.is_human_readable() {
+            return HValueByteArray::deserialize(deserializer).map(HValue);
+        }
+
         let s = String::deserialize(deserializer)?;
 
         s.parse().map_err(D::Error::custom)
@@ -266,11 +319,41 @@ pub fn eg_hmac(key: &HValue, data: &dyn AsRef<[u8]>) -> HValue {
     AsRef::<[u8; 32]>::as_ref(&hmac_sha256.chain(data).finalize_fixed()).into()
 }
 
+/// Hashes a sequence of group elements with [`eg_h`].
+///
+/// The elements are absorbed in order, each encoded as a big-endian byte array
+/// left-padded to the length of `p`, i.e., `H(key; b(elems[0]) | b(elems[1]) | ...)`.
+pub fn hash_group_elements(key: &HValue, elems: &[&GroupElement], group: &Group) -> HValue {
+    let mut v = Vec::with_capacity(elems.len() * group.l_p());
+    for elem in elems {
+        v.extend_from_slice(elem.to_be_bytes_left_pad(group).as_slice());
+    }
+    eg_h(key, &v)
+}
+
+/// Key derivation function in the style of HKDF-Expand (RFC 5869) built on [`eg_h`].
+///
+/// Produces `out_blocks` hash values `T(1), ..., T(out_blocks)` where
+/// `T(i) = H(master; T(i-1) | info | b(i, 4))` and `T(0)` is empty.
+pub fn eg_kdf(master: &HValue, info: &[u8], out_blocks: usize) -> Vec<HValue> {
+    let mut blocks: Vec<HValue> = Vec::with_capacity(out_blocks);
+    for i in 1..=out_blocks {
+        let mut v = Vec::with_capacity(32 + info.len() + 4);
+        if let Some(prev) = blocks.last() {
+            v.extend_from_slice(&prev.0);
+        }
+        v.extend_from_slice(info);
+        v.extend_from_slice(&(i as u32).to_be_bytes());
+        blocks.push(eg_h(master, &v));
+    }
+    blocks
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod test_eg_h {
     use super::*;
-    use std::str::FromStr;
+    use std::{iter::zip, str::FromStr};
 
     #[test]
     fn test_hvalue_std_fmt() {
@@ -282,6 +365,23 @@ mod test_eg_h {
         assert_eq!(format!("{h:?}"), expected);
     }
 
+    #[test]
+    fn test_hvalue_short_debug() {
+        let h: HValue = std::array::from_fn(|ix| ix as u8).into();
+        assert_eq!(h.to_short_debug(), "H(00010203..1C1D1E1F)");
+        assert_eq!(HValue::default().to_short_debug(), "H(00000000..00000000)");
+    }
+
+    #[test]
+    fn test_hvalue_ct_eq() {
+        let h: HValue = std::array::from_fn(|ix| ix as u8).into();
+        let mut h2 = h;
+        assert!(h.ct_eq(&h2));
+        h2.0[31] ^= 1;
+        assert!(!h.ct_eq(&h2));
+        assert!(!h.ct_eq(&HValue::default()));
+    }
+
     #[test]
     fn test_hvalue_serde_json() {
         let h: HValue = std::array::from_fn(|ix| ix as u8).into();
@@ -295,6 +395,37 @@ mod test_eg_h {
         assert_eq!(h2, h);
     }
 
+    #[test]
+    fn test_hvalue_serde_binary() {
+        let h: HValue = std::array::from_fn(|ix| ix as u8).into();
+
+        let bytes = bincode::serialize(&h).unwrap();
+        assert_eq!(bytes.len(), HVALUE_BYTE_LEN);
+        assert_eq!(bytes.as_slice(), h.as_ref());
+
+        let h2: HValue = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(h2, h);
+
+        // JSON still uses the string form.
+        let json = serde_json::to_string(&h).unwrap();
+        assert!(json.starts_with("\"H("));
+        let h3: HValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(h3, h);
+    }
+
+    #[test]
+    fn test_hvalue_byte_conversions() {
+        let bytes: [u8; 32] = std::array::from_fn(|ix| ix as u8);
+
+        let h = HValue::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(h, HValue::from(bytes));
+        assert_eq!(<[u8; 32]>::from(h), bytes);
+
+        assert!(HValue::try_from(&bytes[..31]).is_err());
+        assert!(HValue::try_from([0u8; 33].as_slice()).is_err());
+        assert!(HValue::try_from([0u8; 0].as_slice()).is_err());
+    }
+
     #[test]
     fn test_evaluate_h() {
         let key: HValue = HValue::default();
@@ -309,6 +440,45 @@ mod test_eg_h {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_hash_group_elements() {
+        let election_parameters = crate::example_election_parameters::example_election_parameters();
+        let group = &election_parameters.fixed_parameters.group;
+
+        let key = HValue::default();
+        let g = group.generator();
+        let g2 = g.mul(&g, group);
+
+        let h = hash_group_elements(&key, &[&g, &g2], group);
+        assert_eq!(h, hash_group_elements(&key, &[&g, &g2], group));
+        assert_ne!(h, hash_group_elements(&key, &[&g2, &g], group));
+        assert_ne!(h, hash_group_elements(&key, &[&g], group));
+
+        // Same as hashing the concatenated padded encodings.
+        let mut v = g.to_be_bytes_left_pad(group);
+        v.extend_from_slice(&g2.to_be_bytes_left_pad(group));
+        assert_eq!(h, eg_h(&key, &v));
+    }
+
+    #[test]
+    fn test_eg_kdf() {
+        let master: HValue = std::array::from_fn(|ix| ix as u8).into();
+
+        let blocks = eg_kdf(&master, b"guardian sub-key", 3);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks, eg_kdf(&master, b"guardian sub-key", 3));
+        assert_ne!(blocks[0], blocks[1]);
+        assert_ne!(blocks[1], blocks[2]);
+
+        // A shorter expansion is a prefix of a longer one.
+        assert_eq!(eg_kdf(&master, b"guardian sub-key", 1), blocks[..1]);
+
+        let other = eg_kdf(&master, b"other sub-key", 3);
+        assert!(zip(&blocks, &other).all(|(a, b)| a != b));
+
+        assert!(eg_kdf(&master, b"", 0).is_empty());
+    }
 }
 
 // ElectionGuard "H" function (for WebAssembly)
@@ -35,7 +35,10 @@ pub struct Ciphertext {
 
 /// The encryption nonce used to produce a [`Ciphertext`]
 /// Relevant for producing proofs about the plaintext.
-#[derive(Debug, Clone)]
+///
+/// Serializes `xi` with the same big-endian hex encoding as other [`FieldElement`]s.
+/// As the field is not known during deserialization, call [`Nonce::validate`] afterwards.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Nonce {
     pub xi: FieldElement,
 }
@@ -51,6 +54,16 @@ impl Nonce {
             xi: ScalarField::zero(),
         }
     }
+
+    /// Verifies that `xi` is a valid element of the field, i.e., `xi < q`.
+    /// Useful after deserialization.
+    pub fn validate(&self, fixed_parameters: &FixedParameters) -> Result<()> {
+        ensure!(
+            self.xi.is_valid(&fixed_parameters.field),
+            "Nonce is not a valid field element"
+        );
+        Ok(())
+    }
 }
 
 impl Ciphertext {
@@ -71,6 +84,23 @@ impl Ciphertext {
 
         Ciphertext { alpha, beta }
     }
+
+    /// Re-randomizes a ciphertext by multiplying in a fresh encryption of zero with the given nonce.
+    /// The result decrypts to the same value as `self`.
+    pub fn reencrypt(
+        &self,
+        public_key: &JointElectionPublicKey,
+        fixed_parameters: &FixedParameters,
+        nonce: &Nonce,
+    ) -> Ciphertext {
+        let group = &fixed_parameters.group;
+        let zero = public_key.encrypt_with(fixed_parameters, &nonce.xi, 0);
+
+        Ciphertext {
+            alpha: self.alpha.mul(&zero.alpha, group),
+            beta: self.beta.mul(&zero.beta, group),
+        }
+    }
 }
 
 impl PartialEq for Ciphertext {
@@ -142,6 +172,39 @@ impl JointElectionPublicKey {
         })
     }
 
+    /// Computes the joint election public key `K = prod K_i mod p` from the guardians' public keys `K_i`.
+    ///
+    /// Every `K_i` has to be a member of the group other than the identity. Unlike [`JointElectionPublicKey::compute`],
+    /// this does not check the guardians' proofs of knowledge or that every guardian is represented.
+    pub fn from_guardian_keys(
+        guardian_keys: &[GroupElement],
+        fixed_parameters: &FixedParameters,
+    ) -> Result<Self> {
+        let group = &fixed_parameters.group;
+
+        ensure!(!guardian_keys.is_empty(), "No guardian public keys given");
+        for (ix, k_i) in guardian_keys.iter().enumerate() {
+            ensure!(
+                k_i.is_valid(group),
+                "Guardian public key {} is not a valid group element",
+                ix + 1
+            );
+            ensure!(
+                k_i != &Group::one(),
+                "Guardian public key {} is the identity element",
+                ix + 1
+            );
+        }
+
+        let joint_election_public_key = guardian_keys
+            .iter()
+            .fold(Group::one(), |acc, k_i| acc.mul(k_i, group));
+
+        Ok(Self {
+            joint_election_public_key,
+        })
+    }
+
     pub fn encrypt_with(
         &self,
         fixed_parameters: &FixedParameters,
@@ -209,7 +272,7 @@ impl AsRef<GroupElement> for JointElectionPublicKey {
 mod test {
     use num_bigint::BigUint;
     use util::{
-        algebra::{FieldElement, ScalarField},
+        algebra::{FieldElement, Group, ScalarField},
         algebra_utils::DiscreteLog,
         csprng::Csprng,
     };
@@ -221,7 +284,7 @@ mod test {
         index::Index,
     };
 
-    use super::{Ciphertext, JointElectionPublicKey};
+    use super::{Ciphertext, JointElectionPublicKey, Nonce};
 
     fn g_key(i: u32) -> GuardianSecretKey {
         let mut seed = Vec::new();
@@ -291,4 +354,89 @@ mod test {
 
         assert_eq!(result, factor);
     }
+
+    #[test]
+    fn test_from_guardian_keys() {
+        let election_parameters = example_election_parameters();
+        let fixed_parameters = &election_parameters.fixed_parameters;
+        let group = &fixed_parameters.group;
+
+        let guardian_public_keys: Vec<_> = (1..6).map(|i| g_key(i).make_public_key()).collect();
+        let guardian_keys: Vec<_> = guardian_public_keys
+            .iter()
+            .map(|pk| pk.public_key_k_i_0().clone())
+            .collect();
+
+        let joint_election_public_key =
+            JointElectionPublicKey::from_guardian_keys(&guardian_keys, fixed_parameters).unwrap();
+        assert_eq!(
+            joint_election_public_key,
+            JointElectionPublicKey::compute(&election_parameters, &guardian_public_keys).unwrap()
+        );
+
+        let joint_key_of_two =
+            JointElectionPublicKey::from_guardian_keys(&guardian_keys[..2], fixed_parameters)
+                .unwrap();
+        assert_eq!(
+            joint_key_of_two.joint_election_public_key,
+            guardian_keys[0].mul(&guardian_keys[1], group)
+        );
+
+        assert!(JointElectionPublicKey::from_guardian_keys(&[], fixed_parameters).is_err());
+
+        // The identity is rejected as a guardian key.
+        let mut invalid_keys = guardian_keys;
+        invalid_keys[2] = Group::one();
+        assert!(
+            JointElectionPublicKey::from_guardian_keys(&invalid_keys, fixed_parameters).is_err()
+        );
+    }
+
+    #[test]
+    fn test_reencrypt_ciphertext() {
+        let election_parameters = example_election_parameters();
+        let fixed_parameters = &election_parameters.fixed_parameters;
+        let field = &fixed_parameters.field;
+
+        let guardian_public_keys: Vec<_> =
+            (1..6).map(g_key).map(|sk| sk.make_public_key()).collect();
+
+        let joint_election_public_key =
+            JointElectionPublicKey::compute(&election_parameters, guardian_public_keys.as_slice())
+                .unwrap();
+        let nonce = FieldElement::from(BigUint::from(5u8), field);
+        let encryption = joint_election_public_key.encrypt_with(fixed_parameters, &nonce, 1);
+
+        let fresh_nonce = Nonce::new(FieldElement::from(BigUint::from(7u8), field));
+        let reencryption =
+            encryption.reencrypt(&joint_election_public_key, fixed_parameters, &fresh_nonce);
+        assert_ne!(reencryption, encryption);
+
+        // Re-encrypting with nonce 7 is the same as encrypting with nonce 5 + 7.
+        let nonce_sum = nonce.add(&fresh_nonce.xi, field);
+        assert_eq!(
+            reencryption,
+            joint_election_public_key.encrypt_with(fixed_parameters, &nonce_sum, 1)
+        );
+    }
+
+    #[test]
+    fn test_nonce_serde() {
+        let election_parameters = example_election_parameters();
+        let fixed_parameters = &election_parameters.fixed_parameters;
+        let field = &fixed_parameters.field;
+        let mut csprng = Csprng::new(b"test_nonce_serde");
+
+        let nonce = Nonce::new(field.random_field_elem(&mut csprng));
+        let json = serde_json::to_string(&nonce).unwrap();
+        let nonce_deserialized: Nonce = serde_json::from_str(&json).unwrap();
+        assert_eq!(nonce, nonce_deserialized);
+        assert!(nonce_deserialized.validate(fixed_parameters).is_ok());
+
+        // A value of xi equal to q is not a valid field element.
+        let q = util::base16::to_string_with_prefix(field.order(), 16, None).unwrap();
+        let json = format!("{{\"xi\":\"{q}\"}}");
+        let nonce_out_of_range: Nonce = serde_json::from_str(&json).unwrap();
+        assert!(nonce_out_of_range.validate(fixed_parameters).is_err());
+    }
 }
@@ -10,9 +10,24 @@ use util::algebra::FieldElement;
 use crate::{
     election_manifest::{ContestIndex, ContestOptionIndex},
     election_record::PreVotingData,
-    hash::eg_h,
+    hash::{eg_h, HValue},
+    joint_election_public_key::Nonce,
 };
 
+/// Derives the primary nonce `ξ_B` of a ballot from a secret seed.
+///
+///  ξ_B = H(H_E;"primary_nonce",seed)
+///
+/// The same seed always yields the same primary nonce for a given election, so the seed
+/// must be kept secret and must not be reused across ballots.
+pub fn derive_primary_nonce(header: &PreVotingData, ballot_seed: &HValue) -> Vec<u8> {
+    let mut v = b"primary_nonce".to_vec();
+    v.extend_from_slice(ballot_seed.as_ref());
+
+    let primary_nonce = eg_h(&header.hashes_ext.h_e, &v);
+    primary_nonce.0.to_vec()
+}
+
 /// Generates a nonce for encrypted ballots (Equation 22)
 ///
 ///  ξi,j = H(H_E;20,ξ_B,Λ_i,λ_j)
@@ -34,3 +49,59 @@ pub fn encrypted(
     let nonce = eg_h(&header.hashes_ext.h_e, &v);
     FieldElement::from_bytes_be(nonce.0.as_slice(), field)
 }
+
+/// Generates the nonces `ξ_i,j` (Equation 22) for the first `num_options` options of contest `label_i`
+/// at once, in the order of the option indices.
+///
+/// The nonces are identical to those returned by [`encrypted`] for each option.
+/// At most [`ContestOptionIndex::VALID_MAX_USIZE`] nonces are returned.
+pub fn derive_contest_nonces(
+    header: &PreVotingData,
+    primary_nonce: &[u8],
+    label_i: ContestIndex,
+    num_options: usize,
+) -> Vec<Nonce> {
+    ContestOptionIndex::iter_range_inclusive(ContestOptionIndex::MIN, ContestOptionIndex::MAX)
+        .take(num_options)
+        .map(|label_j| Nonce::new(encrypted(header, primary_nonce, label_i, label_j)))
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::contest_encrypted::test::example_device;
+
+    #[test]
+    fn test_derive_primary_nonce() {
+        let device = example_device();
+
+        let seed_1 = HValue::from([1u8; 32]);
+        let seed_2 = HValue::from([2u8; 32]);
+
+        let nonce_1 = derive_primary_nonce(&device.header, &seed_1);
+        assert_eq!(nonce_1.len(), 32);
+        assert_eq!(nonce_1, derive_primary_nonce(&device.header, &seed_1));
+        assert_ne!(nonce_1, derive_primary_nonce(&device.header, &seed_2));
+    }
+
+    #[test]
+    fn test_derive_contest_nonces() {
+        let device = example_device();
+        let primary_nonce = derive_primary_nonce(&device.header, &HValue::from([1u8; 32]));
+        let contest_index = ContestIndex::from_one_based_index(2).unwrap();
+
+        let nonces = derive_contest_nonces(&device.header, &primary_nonce, contest_index, 4);
+        assert_eq!(nonces.len(), 4);
+        for (j, n) in (1..=4).zip(&nonces) {
+            let option_index = ContestOptionIndex::from_one_based_index(j).unwrap();
+            assert_eq!(
+                n.xi,
+                encrypted(&device.header, &primary_nonce, contest_index, option_index)
+            );
+        }
+
+        assert!(derive_contest_nonces(&device.header, &primary_nonce, contest_index, 0).is_empty());
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright (C) Microsoft Corporation. All rights reserved.
+
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![deny(clippy::manual_assert)]
+
+//! This module provides the encoding of small plaintexts `m` as group elements `K^m`,
+//! where `K` is the joint election public key, and the corresponding decoding for a known
+//! bound on `m`, as well as the combination of decryption shares that precedes the decoding.
+//!
+//! This is the encoding used by [`JointElectionPublicKey::encrypt_with`], which sets
+//! `β = K^(ξ+m)`, so that `β · (A^s)^(-1) = K^m` for a ciphertext `(A, β)`.
+
+use std::collections::HashMap;
+
+use num_bigint::BigUint;
+use util::algebra::{Group, GroupElement};
+
+use crate::{fixed_parameters::FixedParameters, joint_election_public_key::JointElectionPublicKey};
+
+/// Encodes the plaintext `m` as the group element `K^m`.
+pub fn encode_plaintext(
+    m: u64,
+    public_key: &JointElectionPublicKey,
+    fixed_parameters: &FixedParameters,
+) -> GroupElement {
+    let group = &fixed_parameters.group;
+    public_key.as_ref().pow(m, group)
+}
+
+/// Decodes a group element `K^m` to the plaintext `m`, provided that `0 <= m <= max`.
+///
+/// This uses the baby-step giant-step algorithm with steps of size about `sqrt(max)`,
+/// so it is only practical for small values of `max`, such as vote counts.
+/// Returns `None` if there is no such `m`.
+///
+/// [`util::algebra_utils::DiscreteLog`] is not used here because it always precomputes a
+/// table of `2^20` entries for plaintexts up to `2^38`, which is far more work than a tally
+/// bounded by the number of ballots needs.
+pub fn decode_plaintext(
+    elem: &GroupElement,
+    max: u64,
+    public_key: &JointElectionPublicKey,
+    fixed_parameters: &FixedParameters,
+) -> Option<u64> {
+    let group = &fixed_parameters.group;
+    let k = public_key.as_ref();
+
+    // The number of steps, the smallest `m_steps` with `m_steps^2 > max`.
+    let n = max.checked_add(1)?;
+    let m_steps = (1..=n)
+        .find(|s| s.checked_mul(*s).is_none_or(|s2| s2 >= n))
+        .unwrap_or(n);
+
+    // Baby steps: K^j for 0 <= j < m_steps
+    let mut table: HashMap<BigUint, u64> = HashMap::new();
+    let mut k_j = Group::one();
+    for j in 0..m_steps {
+        table.entry(k_j.as_biguint().clone()).or_insert(j);
+        k_j = k_j.mul(k, group);
+    }
+
+    // Giant steps: elem * K^(-i*m_steps) for 0 <= i < m_steps
+    let k_to_minus_m = k.pow(m_steps, group).inv(group)?;
+    let mut gamma = elem.clone();
+    for i in 0..m_steps {
+        if let Some(j) = table.get(gamma.as_biguint()) {
+            let m = i.checked_mul(m_steps)?.checked_add(*j)?;
+            return (m <= max).then_some(m);
+        }
+        gamma = gamma.mul(&k_to_minus_m, group);
+    }
+    None
+}
+
+/// Combines decryption shares `M_i` into `M = prod M_i mod p`.
+///
+/// This is the combination for a secret key that is additively shared among all guardians,
+/// i.e., `s = sum s_i` and `M_i = A^s_i`. For threshold decryption with a subset of the
+/// guardians use [`crate::verifiable_decryption::CombinedDecryptionShare::combine`] instead.
+pub fn combine_decryption_shares(
+    shares: &[GroupElement],
+    fixed_parameters: &FixedParameters,
+) -> GroupElement {
+    let group = &fixed_parameters.group;
+    shares
+        .iter()
+        .fold(Group::one(), |prod, m_i| prod.mul(m_i, group))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::example_election_parameters::example_election_parameters;
+    use util::{
+        algebra::{FieldElement, ScalarField},
+        csprng::Csprng,
+    };
+
+    /// Returns a joint election public key `K = g^s` together with its secret key `s`.
+    fn example_key(
+        fixed_parameters: &FixedParameters,
+        csprng: &mut Csprng,
+    ) -> (JointElectionPublicKey, FieldElement) {
+        let s = fixed_parameters.field.random_field_elem(csprng);
+        let k = fixed_parameters.group.g_exp(&s);
+        let public_key =
+            JointElectionPublicKey::from_guardian_keys(&[k], fixed_parameters).unwrap();
+        (public_key, s)
+    }
+
+    #[test]
+    fn test_encode_decode_plaintext() {
+        let election_parameters = example_election_parameters();
+        let fixed_parameters = &election_parameters.fixed_parameters;
+        let field = &fixed_parameters.field;
+        let group = &fixed_parameters.group;
+        let mut csprng = Csprng::new(b"test_encode_decode_plaintext");
+        let (public_key, s) = example_key(fixed_parameters, &mut csprng);
+
+        // Ciphertexts produced by the encryption decrypt to the encoding of the vote.
+        for m in [0, 1, 7, 10] {
+            let xi = field.random_field_elem(&mut csprng);
+            let ct = public_key.encrypt_with(fixed_parameters, &xi, m as usize);
+            let elem = ct
+                .beta
+                .mul(&ct.alpha.exp(&s, group).inv(group).unwrap(), group);
+            assert_eq!(elem, encode_plaintext(m, &public_key, fixed_parameters));
+            assert_eq!(
+                decode_plaintext(&elem, 10, &public_key, fixed_parameters),
+                Some(m)
+            );
+        }
+
+        for m in 11..=20 {
+            let elem = encode_plaintext(m, &public_key, fixed_parameters);
+            assert_eq!(
+                decode_plaintext(&elem, 10, &public_key, fixed_parameters),
+                None
+            );
+        }
+
+        let elem = encode_plaintext(0, &public_key, fixed_parameters);
+        assert_eq!(
+            decode_plaintext(&elem, 0, &public_key, fixed_parameters),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_combine_decryption_shares() {
+        let election_parameters = example_election_parameters();
+        let fixed_parameters = &election_parameters.fixed_parameters;
+        let field = &fixed_parameters.field;
+        let group = &fixed_parameters.group;
+        let mut csprng = Csprng::new(b"test_combine_decryption_shares");
+
+        // The secret key s = s_1 + s_2 + s_3 is split among three guardians,
+        // and the joint election public key is K = prod K_i = g^s.
+        let secret_shares: Vec<_> = (0..3)
+            .map(|_| field.random_field_elem(&mut csprng))
+            .collect();
+        let s = secret_shares
+            .iter()
+            .fold(ScalarField::zero(), |acc, s_i| acc.add(s_i, field));
+        let guardian_keys: Vec<_> = secret_shares.iter().map(|s_i| group.g_exp(s_i)).collect();
+        let public_key =
+            JointElectionPublicKey::from_guardian_keys(&guardian_keys, fixed_parameters).unwrap();
+        assert_eq!(public_key.as_ref(), &group.g_exp(&s));
+
+        let tally = 7;
+        let xi = field.random_field_elem(&mut csprng);
+        let ct = public_key.encrypt_with(fixed_parameters, &xi, tally as usize);
+
+        // M_i = A^s_i
+        let shares: Vec<_> = secret_shares
+            .iter()
+            .map(|s_i| ct.alpha.exp(s_i, group))
+            .collect();
+        let m = combine_decryption_shares(&shares, fixed_parameters);
+        assert_eq!(m, ct.alpha.exp(&s, group));
+
+        // B * M^(-1) = K^tally
+        let k_tally = ct.beta.mul(&m.inv(group).unwrap(), group);
+        assert_eq!(
+            k_tally,
+            encode_plaintext(tally, &public_key, fixed_parameters)
+        );
+        assert_eq!(
+            decode_plaintext(&k_tally, 10, &public_key, fixed_parameters),
+            Some(tally)
+        );
+
+        assert_eq!(
+            combine_decryption_shares(&[], fixed_parameters),
+            Group::one()
+        );
+    }
+}
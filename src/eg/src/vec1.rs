@@ -7,8 +7,9 @@
 
 use std::collections::TryReserveError;
 
-use anyhow::{ensure, Error, Result};
+use anyhow::{ensure, Result};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
 
 use crate::{index::Index, zk::ProofRange};
 
@@ -24,6 +25,17 @@ impl<T: HasIndexTypeMarker> HasIndexType for T {
     type IndexType = T;
 }
 
+/// Represents errors occurring while adding elements to a [`Vec1`].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Vec1Error {
+    /// Occurs if the `Vec1` already holds the maximum number of elements addressable by its index type.
+    #[error("Vec1 is full, it cannot hold more than {max} elements.")]
+    IndexOverflow { max: usize },
+    /// Occurs if memory for the additional element could not be reserved.
+    #[error("Could not reserve memory for the Vec1: {0}")]
+    Reserve(#[from] TryReserveError),
+}
+
 /// A `Vec`-like container intended to be used when 1-based indexing is required.
 /// It is missing many of the methods of `std::vec::Vec`, and not intended to be a general-purpose
 /// replacement. In particular, the methods that would return slices are not provided, because
@@ -89,9 +101,13 @@ impl<T: HasIndexType> Vec1<T> {
 
     /// Pushes an additional element onto the end of the Vec1, unless
     /// doing so would exceed the size of a `Index<T>`.
+    /// The maximum length of a Vec1 is [`Index::VALID_MAX_USIZE`], i.e., `2^31 - 1`.
     /// Compare to: [`Vec::push`].
-    pub fn try_push(&mut self, value: T) -> Result<()> {
-        ensure!(self.len() < Index::<T>::VALID_MAX_USIZE, "Vec1 is full");
+    pub fn try_push(&mut self, value: T) -> Result<(), Vec1Error> {
+        let max = Index::<T>::VALID_MAX_USIZE;
+        if max <= self.len() {
+            return Err(Vec1Error::IndexOverflow { max });
+        }
 
         self.0.try_reserve(1)?;
         self.0.push(value);
@@ -181,7 +197,7 @@ impl<T> IntoIterator for Vec1<T> {
 /// Attempt to create a [`Vec1<T>`] from a [`Vec<T>`].
 /// This will fail if the source has 2^31 or more elements.
 impl<T: HasIndexType> std::convert::TryFrom<std::vec::Vec<T>> for Vec1<T> {
-    type Error = Error;
+    type Error = anyhow::Error;
     fn try_from(v: std::vec::Vec<T>) -> Result<Self> {
         ensure!(
             v.len() <= Index::<T>::VALID_MAX_USIZE,
@@ -197,7 +213,7 @@ impl<T: HasIndexType> std::convert::TryFrom<std::vec::Vec<T>> for Vec1<T> {
 /// It is hoped that someday Rust's const generics feature will have improved to
 /// the point that we can prove this at compile time, and implement [`From`] instead.
 impl<T: HasIndexType, const N: usize> std::convert::TryFrom<[T; N]> for Vec1<T> {
-    type Error = Error;
+    type Error = anyhow::Error;
     fn try_from(arr: [T; N]) -> Result<Self> {
         let v: std::vec::Vec<T> = arr.into();
         v.try_into()
@@ -285,4 +301,19 @@ mod test_vec1 {
         );
         assert_eq!(iter.next(), None);
     }
+
+    impl HasIndexTypeMarker for () {}
+
+    #[test]
+    fn test_try_push_full() {
+        // Zero-sized elements do not need any allocation, so a full Vec1 is cheap.
+        let max = Index::<()>::VALID_MAX_USIZE;
+        let mut vec1: Vec1<()> = vec![(); max - 1].try_into().unwrap();
+
+        vec1.try_push(()).unwrap();
+        assert_eq!(vec1.len(), max);
+
+        assert_eq!(vec1.try_push(()), Err(Vec1Error::IndexOverflow { max }));
+        assert_eq!(vec1.len(), max);
+    }
 }
@@ -60,6 +60,57 @@ impl DecryptionShare {
             m_i,
         }
     }
+
+    /// This function computes the public key share `K_i = g^P(i)` of guardian `i`
+    /// from the guardians' published coefficient commitments.
+    ///
+    /// That is `K_i = prod_j prod_m K_{j,m}^(i^m)`, cf. Equation `21`.
+    ///
+    /// The arguments are
+    /// - `fixed_parameters` - the fixed parameters
+    /// - `guardian_public_keys` - the [`GuardianPublicKey`]s of all guardians
+    /// - `i` - the guardian's index
+    pub fn public_key_share(
+        fixed_parameters: &FixedParameters,
+        guardian_public_keys: &[GuardianPublicKey],
+        i: GuardianIndex,
+    ) -> GroupElement {
+        let field = &fixed_parameters.field;
+        let group = &fixed_parameters.group;
+
+        let i = FieldElement::from(i.get_one_based_u32(), field);
+        guardian_public_keys
+            .iter()
+            .flat_map(|pk| (0u32..).zip(&pk.coefficient_commitments.0))
+            .fold(Group::one(), |prod, (m, k_j_m)| {
+                let i_pow_m = i.pow(m, field);
+                prod.mul(&k_j_m.0.exp(&i_pow_m, group), group)
+            })
+    }
+
+    /// This function verifies the [`DecryptionShare`] with a [`ProofCorrectDecryption`]
+    /// against the public key share of guardian `self.i` derived from the guardians'
+    /// published coefficient commitments.
+    ///
+    /// The arguments are
+    /// - `self` - the decryption share
+    /// - `fixed_parameters` - the fixed parameters
+    /// - `h_e` - the extended bash hash
+    /// - `guardian_public_keys` - the [`GuardianPublicKey`]s of all guardians
+    /// - `ciphertext` - the ciphertext
+    /// - `proof` - the proof of correct decryption of the share
+    pub fn verify(
+        &self,
+        fixed_parameters: &FixedParameters,
+        h_e: &HashesExt,
+        guardian_public_keys: &[GuardianPublicKey],
+        ciphertext: &Ciphertext,
+        proof: &ProofCorrectDecryption,
+    ) -> bool {
+        let public_key_share =
+            Self::public_key_share(fixed_parameters, guardian_public_keys, self.i);
+        proof.verify(fixed_parameters, h_e, &public_key_share, ciphertext, self)
+    }
 }
 
 /// The combined decryption share allows to compute the plain-text from a given
@@ -547,6 +598,140 @@ impl DecryptionProof {
     }
 }
 
+/// A proof that a single guardian's [`DecryptionShare`] was computed correctly.
+///
+/// This is a Chaum–Pedersen proof that `log_g(K_i) == log_A(M_i)`, where
+/// `K_i = g^P(i)` is the guardian's public key share, `A` is the first
+/// component of the ciphertext, and `M_i` is the decryption share. It allows
+/// checking a share on its own, before the shares are combined.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProofCorrectDecryption {
+    /// Challenge
+    pub challenge: FieldElement,
+    /// Response
+    pub response: FieldElement,
+}
+
+impl ProofCorrectDecryption {
+    /// This function computes the challenge for the proof.
+    ///
+    /// The hashed values are laid out like those of Equation `71`, with the
+    /// guardian's public key share `k_i` and decryption share `m_i` in place of
+    /// the joint election public key and the combined decryption share. The
+    /// domain separator is `0x38` instead of `0x30`, so that the challenge of a
+    /// share proof can never coincide with that of a [`DecryptionProof`] for a
+    /// joint key equal to `k_i`. No other hash in this crate uses `0x38`.
+    fn challenge(
+        fixed_parameters: &FixedParameters,
+        h_e: &HValue,
+        k_i: &GroupElement,
+        c: &Ciphertext,
+        a: &GroupElement,
+        b: &GroupElement,
+        m_i: &GroupElement,
+    ) -> FieldElement {
+        let field = &fixed_parameters.field;
+        let group = &fixed_parameters.group;
+        // v = 0x38 | b(k_i,512) | b(c.A,512)| b(c.B,512) | b(a,512) | b(b,512) | b(m_i,512)
+        let mut v = vec![0x38];
+        v.extend_from_slice(k_i.to_be_bytes_left_pad(group).as_slice());
+        v.extend_from_slice(c.alpha.to_be_bytes_left_pad(group).as_slice());
+        v.extend_from_slice(c.beta.to_be_bytes_left_pad(group).as_slice());
+        v.extend_from_slice(a.to_be_bytes_left_pad(group).as_slice());
+        v.extend_from_slice(b.to_be_bytes_left_pad(group).as_slice());
+        v.extend_from_slice(m_i.to_be_bytes_left_pad(group).as_slice());
+        let c = eg_h(h_e, &v);
+        FieldElement::from_bytes_be(c.0.as_slice(), field)
+    }
+
+    /// This function computes a [`ProofCorrectDecryption`] for the given
+    /// [`DecryptionShare`].
+    ///
+    /// The arguments are
+    /// - `csprng` - secure randomness generator
+    /// - `fixed_parameters` - the fixed parameters
+    /// - `h_e` - the extended bash hash
+    /// - `secret_key_share` - the guardian's key share
+    /// - `ciphertext` - the ciphertext
+    /// - `decryption_share` - the decryption share computed from `secret_key_share`
+    pub fn new(
+        csprng: &mut Csprng,
+        fixed_parameters: &FixedParameters,
+        h_e: &HashesExt,
+        secret_key_share: &GuardianSecretKeyShare,
+        ciphertext: &Ciphertext,
+        decryption_share: &DecryptionShare,
+    ) -> Self {
+        let group = &fixed_parameters.group;
+        let field = &fixed_parameters.field;
+
+        let k_i = group.g_exp(&secret_key_share.p_i);
+        let u = field.random_field_elem(csprng);
+        let a = group.g_exp(&u);
+        let b = ciphertext.alpha.exp(&u, group);
+        let challenge = Self::challenge(
+            fixed_parameters,
+            &h_e.h_e,
+            &k_i,
+            ciphertext,
+            &a,
+            &b,
+            &decryption_share.m_i,
+        );
+        let response = u.sub(&challenge.mul(&secret_key_share.p_i, field), field);
+        ProofCorrectDecryption {
+            challenge,
+            response,
+        }
+    }
+
+    /// This function verifies a [`ProofCorrectDecryption`].
+    ///
+    /// The arguments are
+    /// - `self` - the proof
+    /// - `fixed_parameters` - the fixed parameters
+    /// - `h_e` - the extended bash hash
+    /// - `public_key_share` - the guardian's public key share `K_i = g^P(i)`
+    /// - `ciphertext` - the ciphertext
+    /// - `decryption_share` - the decryption share
+    pub fn verify(
+        &self,
+        fixed_parameters: &FixedParameters,
+        h_e: &HashesExt,
+        public_key_share: &GroupElement,
+        ciphertext: &Ciphertext,
+        decryption_share: &DecryptionShare,
+    ) -> bool {
+        let group = &fixed_parameters.group;
+        let field = &fixed_parameters.field;
+
+        if !self.challenge.is_valid(field) || !self.response.is_valid(field) {
+            return false;
+        }
+        if !decryption_share.m_i.is_valid(group) {
+            return false;
+        }
+
+        let a = group
+            .g_exp(&self.response)
+            .mul(&public_key_share.exp(&self.challenge, group), group);
+        let b = ciphertext
+            .alpha
+            .exp(&self.response, group)
+            .mul(&decryption_share.m_i.exp(&self.challenge, group), group);
+        let c = Self::challenge(
+            fixed_parameters,
+            &h_e.h_e,
+            public_key_share,
+            ciphertext,
+            &a,
+            &b,
+            &decryption_share.m_i,
+        );
+        c == self.challenge
+    }
+}
+
 /// Represents errors occurring during decryption.
 #[derive(Error, Debug)]
 pub enum DecryptionError {
@@ -740,7 +925,10 @@ mod test {
         verifiable_decryption::ShareCombinationError,
     };
 
-    use super::{CombinedDecryptionShare, DecryptionProof, DecryptionShare, VerifiableDecryption};
+    use super::{
+        CombinedDecryptionShare, DecryptionProof, DecryptionShare, ProofCorrectDecryption,
+        VerifiableDecryption,
+    };
 
     fn key_setup(
         csprng: &mut Csprng,
@@ -967,4 +1155,116 @@ mod test {
         );
         assert!(decryption.verify(fixed_parameters, &h_e, &joint_key, &ciphertext))
     }
+
+    #[test]
+    fn test_proof_correct_decryption() {
+        let mut csprng = Csprng::new(b"test_proof_correct_decryption");
+        let election_parameters = example_election_parameters();
+        let fixed_parameters = &election_parameters.fixed_parameters;
+        let field = &fixed_parameters.field;
+        let group = &fixed_parameters.group;
+
+        let (joint_key, _, key_shares) = key_setup(&mut csprng, &election_parameters);
+
+        let hashes = Hashes::compute(
+            &election_parameters,
+            &example_election_manifest::example_election_manifest(),
+        )
+        .unwrap();
+        let h_e = HashesExt::compute(&election_parameters, &hashes, &joint_key);
+
+        let nonce = field.random_field_elem(&mut csprng);
+        let ciphertext = joint_key.encrypt_with(fixed_parameters, &nonce, 7);
+
+        for ks in key_shares.iter() {
+            let public_key_share = group.g_exp(&ks.p_i);
+            let share = DecryptionShare::from(fixed_parameters, ks, &ciphertext);
+            let proof = ProofCorrectDecryption::new(
+                &mut csprng,
+                fixed_parameters,
+                &h_e,
+                ks,
+                &ciphertext,
+                &share,
+            );
+            assert!(proof.verify(
+                fixed_parameters,
+                &h_e,
+                &public_key_share,
+                &ciphertext,
+                &share
+            ));
+
+            // A tampered share does not verify.
+            let tampered_share = DecryptionShare {
+                i: share.i,
+                m_i: share.m_i.mul(&group.generator(), group),
+            };
+            assert!(!proof.verify(
+                fixed_parameters,
+                &h_e,
+                &public_key_share,
+                &ciphertext,
+                &tampered_share
+            ));
+        }
+    }
+
+    #[test]
+    fn test_verify_decryption_share() {
+        let mut csprng = Csprng::new(b"test_verify_decryption_share");
+        let election_parameters = example_election_parameters();
+        let fixed_parameters = &election_parameters.fixed_parameters;
+        let field = &fixed_parameters.field;
+        let group = &fixed_parameters.group;
+
+        let (joint_key, public_keys, key_shares) = key_setup(&mut csprng, &election_parameters);
+
+        let hashes = Hashes::compute(
+            &election_parameters,
+            &example_election_manifest::example_election_manifest(),
+        )
+        .unwrap();
+        let h_e = HashesExt::compute(&election_parameters, &hashes, &joint_key);
+
+        let nonce = field.random_field_elem(&mut csprng);
+        let ciphertext = joint_key.encrypt_with(fixed_parameters, &nonce, 3);
+
+        for ks in key_shares.iter() {
+            // The public key share derived from the commitments matches the secret key share.
+            assert_eq!(
+                DecryptionShare::public_key_share(fixed_parameters, &public_keys, ks.i),
+                group.g_exp(&ks.p_i)
+            );
+
+            let share = DecryptionShare::from(fixed_parameters, ks, &ciphertext);
+            let proof = ProofCorrectDecryption::new(
+                &mut csprng,
+                fixed_parameters,
+                &h_e,
+                ks,
+                &ciphertext,
+                &share,
+            );
+            assert!(share.verify(fixed_parameters, &h_e, &public_keys, &ciphertext, &proof));
+
+            // The share does not verify when attributed to a different guardian.
+            let other_i = key_shares
+                .iter()
+                .map(|other| other.i)
+                .find(|&i| i != ks.i)
+                .unwrap();
+            let mismatched_share = DecryptionShare {
+                i: other_i,
+                m_i: share.m_i.clone(),
+            };
+            assert!(!mismatched_share.verify(
+                fixed_parameters,
+                &h_e,
+                &public_keys,
+                &ciphertext,
+                &proof
+            ));
+        }
+    }
 }
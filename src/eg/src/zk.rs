@@ -16,9 +16,10 @@ use util::{
 
 use crate::{
     election_record::PreVotingData,
-    hash::eg_h,
+    fixed_parameters::FixedParameters,
+    hash::{eg_h, HValue},
     index::Index,
-    joint_election_public_key::{Ciphertext, Nonce},
+    joint_election_public_key::{Ciphertext, JointElectionPublicKey, Nonce},
     vec1::HasIndexTypeMarker,
 };
 use thiserror::Error;
@@ -56,35 +57,87 @@ impl ProofRange {
     /// - `ct` - the ciphertext
     /// - `a` - the a vector of the commit message
     /// - `b` - the b vector of the commit message
+    ///
+    /// The hash is keyed with `H_E` and the data is serialized in the following order, where every
+    /// group element is encoded as a big-endian byte array of the length of `p`:
+    /// - the domain separator `0x21`
+    /// - the joint election public key `K`
+    /// - the ciphertext `alpha` and `beta`
+    /// - the commitments `a_0, ..., a_L`
+    /// - the commitments `b_0, ..., b_L`
     pub fn challenge(
         pvd: &PreVotingData,
         ct: &Ciphertext,
         a: &[GroupElement],
         b: &[GroupElement],
     ) -> FieldElement {
+        proof_challenge(
+            &pvd.hashes_ext.h_e,
+            &pvd.public_key,
+            ct,
+            a,
+            b,
+            &pvd.parameters.fixed_parameters,
+        )
+    }
+
+    /// This function computes the hash value of Equation `46` from which the challenge is derived.
+    fn challenge_hash(
+        pvd: &PreVotingData,
+        ct: &Ciphertext,
+        a: &[GroupElement],
+        b: &[GroupElement],
+    ) -> HValue {
+        proof_challenge_hash(
+            &pvd.hashes_ext.h_e,
+            &pvd.public_key,
+            ct,
+            a,
+            b,
+            &pvd.parameters.fixed_parameters,
+        )
+    }
+
+    /// This function returns the Fiat–Shamir transcript hash of the [`ProofRange`], i.e., the hash value
+    /// of Equation `46` over the commitments recomputed from the proof as in Verification (5.1) and (5.2).
+    ///
+    /// For a valid proof the challenge derived from this hash equals the sum of the `c_j`. The
+    /// serialization order is documented on [`ProofRange::challenge`].
+    pub fn transcript_hash(&self, pvd: &PreVotingData, ct: &Ciphertext) -> HValue {
+        let (a, b) = self.commitments(pvd, ct);
+        Self::challenge_hash(pvd, ct, &a, &b)
+    }
+
+    /// Recomputes the commit message `(a, b)` from the proof as in Verification (5.1) and (5.2).
+    fn commitments(
+        &self,
+        pvd: &PreVotingData,
+        ct: &Ciphertext,
+    ) -> (Vec<GroupElement>, Vec<GroupElement>) {
         let field = &pvd.parameters.fixed_parameters.field;
         let group = &pvd.parameters.fixed_parameters.group;
 
-        // v = 0x21 | b(K,512) | b(alpha,4) | b(beta,512) | b(a_0,512) | ... | b(b_L,512) for standard parameters
-        let mut v = vec![0x21];
-        v.extend_from_slice(
-            pvd.public_key
-                .joint_election_public_key
-                .to_be_bytes_left_pad(group)
-                .as_slice(),
-        );
-        v.extend_from_slice(ct.alpha.to_be_bytes_left_pad(group).as_slice());
-        v.extend_from_slice(ct.beta.to_be_bytes_left_pad(group).as_slice());
-        a.iter().for_each(|a_i| {
-            v.extend_from_slice(a_i.to_be_bytes_left_pad(group).as_slice());
-        });
-        b.iter().for_each(|b_i| {
-            v.extend_from_slice(b_i.to_be_bytes_left_pad(group).as_slice());
-        });
-
-        // Equation `46`
-        let c = eg_h(&pvd.hashes_ext.h_e, &v);
-        FieldElement::from_bytes_be(c.0.as_slice(), field)
+        // (5.1)
+        let a = self
+            .0
+            .iter()
+            .map(|pf| group.g_exp(&pf.v).mul(&ct.alpha.exp(&pf.c, group), group))
+            .collect::<Vec<GroupElement>>();
+        // (5.2)
+        let b = self
+            .0
+            .iter()
+            .enumerate()
+            .map(|(j, pf)| {
+                let j_scalar = FieldElement::from(j, field);
+                let w = pf.v.sub(&j_scalar.mul(&pf.c, field), field);
+                let k_w = pvd.public_key.joint_election_public_key.exp(&w, group);
+                let b_c = ct.beta.exp(&pf.c, group);
+                k_w.mul(&b_c, group)
+            })
+            .collect::<Vec<GroupElement>>();
+
+        (a, b)
     }
 
     /// This function computes a [`ProofRange`] from given [`Ciphertext`] and encrypted `small_l`.
@@ -156,6 +209,20 @@ impl ProofRange {
         ))
     }
 
+    /// Returns a canonical byte encoding of the [`ProofRange`].
+    ///
+    /// The encoding is the number of pairs `(c_j, v_j)` as a 4-byte big-endian integer followed by
+    /// `c_0 | v_0 | ... | c_L | v_L`, where every field element is left-padded to the length of `q`.
+    pub fn to_canonical_bytes(&self, field: &ScalarField) -> Vec<u8> {
+        let mut v = Vec::new();
+        v.extend_from_slice(&(self.0.len() as u32).to_be_bytes());
+        for pf in &self.0 {
+            v.extend_from_slice(pf.c.to_be_bytes_left_pad(field).as_slice());
+            v.extend_from_slice(pf.v.to_be_bytes_left_pad(field).as_slice());
+        }
+        v
+    }
+
     /// This function verifies a [`ProofRange`] with respect to a given [`Ciphertext`] and context.
     ///
     /// The arguments are
@@ -167,32 +234,31 @@ impl ProofRange {
     /// This is essentially Verification (5).
     pub fn verify(&self, pvd: &PreVotingData, ct: &Ciphertext, big_l: usize) -> bool {
         let field = &pvd.parameters.fixed_parameters.field;
+
+        // (5.1), (5.2) and (5.3)
+        let c = FieldElement::from_bytes_be(self.transcript_hash(pvd, ct).0.as_slice(), field);
+
+        self.verify_with_challenge(pvd, ct, big_l, &c)
+    }
+
+    /// This function verifies a [`ProofRange`] against an externally supplied challenge `c`,
+    /// instead of recomputing it from the transcript as [`ProofRange::verify`] does.
+    ///
+    /// The caller is responsible for deriving `c` from [`ProofRange::transcript_hash`], e.g., as
+    /// part of a batch verification.
+    pub fn verify_with_challenge(
+        &self,
+        pvd: &PreVotingData,
+        ct: &Ciphertext,
+        big_l: usize,
+        c: &FieldElement,
+    ) -> bool {
+        let field = &pvd.parameters.fixed_parameters.field;
         let group = &pvd.parameters.fixed_parameters.group;
 
-        // (5.1)
-        let a = (0..big_l + 1)
-            .map(|j| {
-                group
-                    .g_exp(&self.0[j].v)
-                    .mul(&ct.alpha.exp(&self.0[j].c, group), group)
-            })
-            .collect::<Vec<GroupElement>>();
-        // (5.2)
-        let w = (0..big_l + 1)
-            .map(|j| {
-                let j_scalar = FieldElement::from(j, field);
-                self.0[j].v.sub(&j_scalar.mul(&self.0[j].c, field), field)
-            })
-            .collect::<Vec<FieldElement>>();
-        let b = (0..big_l + 1)
-            .map(|j| {
-                let k_w = pvd.public_key.joint_election_public_key.exp(&w[j], group);
-                let b_c = ct.beta.exp(&self.0[j].c, group);
-                k_w.mul(&b_c, group)
-            })
-            .collect::<Vec<GroupElement>>();
-        // (5.3)
-        let c = Self::challenge(pvd, ct, &a, &b);
+        if self.0.len() != big_l + 1 {
+            return false;
+        }
 
         // Verification check (5.A) alpha, beta are valid group elements
         if !ct.alpha.is_valid(group) || !ct.beta.is_valid(group) {
@@ -215,10 +281,66 @@ impl ProofRange {
             .0
             .iter()
             .fold(ScalarField::zero(), |acc, pf| acc.add(&pf.c, field));
-        c == rhs
+        c == &rhs
     }
 }
 
+/// This function computes the challenge of a [`ProofRange`] as specified in Equation `46`
+/// without requiring the full [`PreVotingData`], e.g., for external verifiers.
+///
+/// It equals [`ProofRange::challenge`] for `h_e` and `public_key` taken from the pre-voting data.
+///
+/// The arguments are
+/// - `h_e` - the extended base hash
+/// - `public_key` - the joint election public key
+/// - `ct` - the ciphertext
+/// - `a` - the a vector of the commit message
+/// - `b` - the b vector of the commit message
+/// - `fixed_parameters` - the fixed parameters
+pub fn proof_challenge(
+    h_e: &HValue,
+    public_key: &JointElectionPublicKey,
+    ct: &Ciphertext,
+    a: &[GroupElement],
+    b: &[GroupElement],
+    fixed_parameters: &FixedParameters,
+) -> FieldElement {
+    let c = proof_challenge_hash(h_e, public_key, ct, a, b, fixed_parameters);
+    FieldElement::from_bytes_be(c.0.as_slice(), &fixed_parameters.field)
+}
+
+/// This function computes the hash value of Equation `46` from which the challenge is derived.
+fn proof_challenge_hash(
+    h_e: &HValue,
+    public_key: &JointElectionPublicKey,
+    ct: &Ciphertext,
+    a: &[GroupElement],
+    b: &[GroupElement],
+    fixed_parameters: &FixedParameters,
+) -> HValue {
+    let group = &fixed_parameters.group;
+
+    // v = 0x21 | b(K,512) | b(alpha,4) | b(beta,512) | b(a_0,512) | ... | b(b_L,512) for standard parameters
+    let mut v = vec![0x21];
+    v.extend_from_slice(
+        public_key
+            .joint_election_public_key
+            .to_be_bytes_left_pad(group)
+            .as_slice(),
+    );
+    v.extend_from_slice(ct.alpha.to_be_bytes_left_pad(group).as_slice());
+    v.extend_from_slice(ct.beta.to_be_bytes_left_pad(group).as_slice());
+    a.iter().for_each(|a_i| {
+        v.extend_from_slice(a_i.to_be_bytes_left_pad(group).as_slice());
+    });
+    b.iter().for_each(|b_i| {
+        v.extend_from_slice(b_i.to_be_bytes_left_pad(group).as_slice());
+    });
+
+    // Equation `46`
+    eg_h(h_e, &v)
+}
+
 /*
 #[derive(Debug, Clone)]
 pub struct ProofGuardian {
@@ -386,3 +508,88 @@ impl ProofGuardian {
     }
 }
 */
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use util::{
+        algebra::{FieldElement, ScalarField},
+        csprng::Csprng,
+    };
+
+    use super::{proof_challenge, ProofRange};
+    use crate::{contest_encrypted::test::example_device, joint_election_public_key::Nonce};
+
+    #[test]
+    fn test_transcript_hash() {
+        let mut csprng = Csprng::new(b"test_transcript_hash");
+        let pvd = example_device().header;
+        let fixed_parameters = &pvd.parameters.fixed_parameters;
+        let field = &fixed_parameters.field;
+
+        let nonce = Nonce::new(field.random_field_elem(&mut csprng));
+        let ct = pvd.public_key.encrypt_with(fixed_parameters, &nonce.xi, 1);
+        let proof = ProofRange::new(&pvd, &mut csprng, &ct, &nonce, 1, 2).unwrap();
+        assert!(proof.verify(&pvd, &ct, 2));
+        assert!(!proof.verify(&pvd, &ct, 1));
+
+        // The challenge derived from the transcript is what verification compares against.
+        let transcript = proof.transcript_hash(&pvd, &ct);
+        let c = FieldElement::from_bytes_be(transcript.0.as_slice(), field);
+        let c_sum = proof
+            .0
+            .iter()
+            .fold(ScalarField::zero(), |acc, pf| acc.add(&pf.c, field));
+        assert_eq!(c, c_sum);
+
+        assert!(proof.verify_with_challenge(&pvd, &ct, 2, &c));
+        let wrong_c = c.add(&ScalarField::one(), field);
+        assert!(!proof.verify_with_challenge(&pvd, &ct, 2, &wrong_c));
+
+        // A different ciphertext yields a different transcript.
+        let other_ct = pvd.public_key.encrypt_with(fixed_parameters, &nonce.xi, 0);
+        assert_ne!(transcript, proof.transcript_hash(&pvd, &other_ct));
+    }
+
+    #[test]
+    fn test_proof_challenge() {
+        let mut csprng = Csprng::new(b"test_proof_challenge");
+        let pvd = example_device().header;
+        let fixed_parameters = &pvd.parameters.fixed_parameters;
+        let field = &fixed_parameters.field;
+
+        let nonce = Nonce::new(field.random_field_elem(&mut csprng));
+        let ct = pvd.public_key.encrypt_with(fixed_parameters, &nonce.xi, 1);
+        let proof = ProofRange::new(&pvd, &mut csprng, &ct, &nonce, 1, 1).unwrap();
+
+        // The challenge used by `ProofRange::new` is the sum of the individual challenges.
+        let (a, b) = proof.commitments(&pvd, &ct);
+        let c = proof_challenge(
+            &pvd.hashes_ext.h_e,
+            &pvd.public_key,
+            &ct,
+            &a,
+            &b,
+            fixed_parameters,
+        );
+        let c_sum = proof
+            .0
+            .iter()
+            .fold(ScalarField::zero(), |acc, pf| acc.add(&pf.c, field));
+        assert_eq!(c, c_sum);
+        assert_eq!(c, ProofRange::challenge(&pvd, &ct, &a, &b));
+
+        // Swapping the commitments changes the challenge.
+        assert_ne!(
+            c,
+            proof_challenge(
+                &pvd.hashes_ext.h_e,
+                &pvd.public_key,
+                &ct,
+                &b,
+                &a,
+                fixed_parameters
+            )
+        );
+    }
+}
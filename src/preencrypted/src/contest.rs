@@ -7,7 +7,7 @@
 
 use eg::{
     contest_encrypted::ContestEncrypted,
-    contest_selection::ContestSelectionPlaintext,
+    contest_selection::{ContestSelectionPlaintext, VoteCount},
     device::Device,
     election_manifest::{Contest, ContestIndex, ContestOptionIndex},
     election_record::PreVotingData,
@@ -234,14 +234,11 @@ impl ContestPreEncrypted {
                 .unwrap();
         }
 
-        let mut num_selections = 0;
-        voter_selections.iter().for_each(|v| num_selections += v);
-
         let proof_selection_limit = ContestEncrypted::proof_selection_limit(
             &device.header,
             csprng,
             &selection,
-            num_selections as usize,
+            VoteCount::sum_of(voter_selections),
             selection_limit,
         )?;
         let selection = selection.iter().map(|(ct, _)| ct.clone()).collect();
@@ -7,6 +7,7 @@
 
 use crate::{
     algebra_utils::{cnt_bits_repr, mod_inverse, to_be_bytes_left_pad},
+    bitwise::ct_eq_bytes,
     csprng::Csprng,
     prime::is_prime,
 };
@@ -77,12 +78,75 @@ impl FieldElement {
         mod_inverse(&self.0, &field.q).map(FieldElement)
     }
 
+    /// Computes the multiplicative inverses of all given field elements.
+    ///
+    /// This uses Montgomery's trick and therefore requires only a single inversion in `Z_q`.
+    /// If any of the elements is not invertible, i.e., zero, the positions of all such elements
+    /// are returned instead.
+    pub fn try_batch_invert(
+        elems: &[FieldElement],
+        field: &ScalarField,
+    ) -> Result<Vec<FieldElement>, Vec<usize>> {
+        let zeros: Vec<usize> = elems
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| (&x.0 % &field.q).is_zero())
+            .map(|(i, _)| i)
+            .collect();
+        if !zeros.is_empty() {
+            return Err(zeros);
+        }
+
+        // prefix[i] = elems[0] * ... * elems[i-1]
+        let mut prefix = Vec::with_capacity(elems.len());
+        let mut acc = ScalarField::one();
+        for x in elems {
+            prefix.push(acc.clone());
+            acc = acc.mul(x, field);
+        }
+
+        // The product is only non-invertible if q is not prime.
+        let Some(mut acc_inv) = acc.inv(field) else {
+            return Err((0..elems.len())
+                .filter(|&i| elems[i].inv(field).is_none())
+                .collect());
+        };
+
+        let mut inverses = vec![ScalarField::zero(); elems.len()];
+        for (i, x) in elems.iter().enumerate().rev() {
+            inverses[i] = acc_inv.mul(&prefix[i], field);
+            acc_inv = acc_inv.mul(x, field);
+        }
+        Ok(inverses)
+    }
+
     /// Performs modular exponentiation of the field element with a given integer exponent.
     pub fn pow(&self, exponent: impl Into<BigUint>, field: &ScalarField) -> FieldElement {
         let x = exponent.into();
         FieldElement(self.0.modpow(&x, &field.q))
     }
 
+    /// Performs modular exponentiation of the field element with a small exponent
+    /// by square-and-multiply, without converting the exponent into a [`BigUint`].
+    pub fn pow_u64(&self, exponent: u64, field: &ScalarField) -> FieldElement {
+        let mut result = ScalarField::one();
+        for i in (0..u64::BITS - exponent.leading_zeros()).rev() {
+            result = result.square(field);
+            if (exponent >> i) & 1 == 1 {
+                result = result.mul(self, field);
+            }
+        }
+        // Make sure the result is reduced even if q = 1.
+        FieldElement(result.0 % &field.q)
+    }
+
+    /// Squares the field element.
+    ///
+    /// That is the function computes `self^2 % q` where `q` is the field order.
+    pub fn square(&self, field: &ScalarField) -> Self {
+        self.mul(self, field)
+    }
+
     /// Creates a field element from a given integer.
     pub fn from<T>(x: T, field: &ScalarField) -> Self
     where
@@ -129,6 +193,22 @@ impl FieldElement {
     }
 }
 
+/// Compares the (reduced) value of the field element with an integer.
+///
+/// The integer is *not* reduced modulo `q`, as the field is not known here.
+/// Hence `x == v` only holds if `v < q`.
+impl PartialEq<u64> for FieldElement {
+    fn eq(&self, other: &u64) -> bool {
+        self.0 == BigUint::from(*other)
+    }
+}
+
+impl PartialEq<FieldElement> for u64 {
+    fn eq(&self, other: &FieldElement) -> bool {
+        other == self
+    }
+}
+
 impl ScalarField {
     /// Constructs a new scalar field from a given order.
     ///
@@ -248,6 +328,50 @@ impl GroupElement {
         GroupElement(self.0.modpow(&exponent.0, &group.p))
     }
 
+    /// Performs modular exponentiation of the group element with each of the given field elements.
+    ///
+    /// This is equivalent to mapping [`GroupElement::exp`] over `exponents`, but precomputes a
+    /// fixed-base window table for `self` once, which amortizes over many exponents.
+    pub fn batch_exp(&self, exponents: &[FieldElement], group: &Group) -> Vec<GroupElement> {
+        const WINDOW_BITS: u64 = 4;
+        const WINDOW_SIZE: usize = 1 << WINDOW_BITS;
+
+        let max_bits = exponents.iter().map(|x| x.0.bits()).max().unwrap_or(0);
+        let cnt_windows = max_bits.div_ceil(WINDOW_BITS) as usize;
+
+        // table[i][d] = self^(d * 2^(WINDOW_BITS * i))
+        let mut table: Vec<Vec<BigUint>> = Vec::with_capacity(cnt_windows);
+        let mut base = self.0.clone() % &group.p;
+        for _ in 0..cnt_windows {
+            let mut row = Vec::with_capacity(WINDOW_SIZE);
+            row.push(BigUint::one());
+            for d in 1..WINDOW_SIZE {
+                row.push((&row[d - 1] * &base) % &group.p);
+            }
+            base = (&row[WINDOW_SIZE - 1] * &base) % &group.p;
+            table.push(row);
+        }
+
+        exponents
+            .iter()
+            .map(|x| {
+                // The windows of the exponent are the nibbles of its little-endian bytes.
+                let nibbles =
+                    x.0.to_bytes_le()
+                        .into_iter()
+                        .flat_map(|b| [b & 0xF, b >> 4]);
+                let result = table
+                    .iter()
+                    .zip(nibbles)
+                    .filter(|(_, d)| *d != 0)
+                    .fold(BigUint::one() % &group.p, |acc, (row, d)| {
+                        (acc * &row[d as usize]) % &group.p
+                    });
+                GroupElement(result)
+            })
+            .collect()
+    }
+
     /// Checks if the element is a valid member of the given group.
     ///
     /// This method return true iff `0 <= self < p` and `self^q % p == 1` where `p` is the group modulus and `q` the group order.
@@ -265,6 +389,17 @@ impl GroupElement {
         to_be_bytes_left_pad(&self.0, group.l_p())
     }
 
+    /// Compares two group elements via their fixed-length encodings without short-circuiting
+    /// on the first differing byte. Use this instead of `==` for secret-derived elements.
+    ///
+    /// Both elements are expected to be valid members of the given group.
+    pub fn ct_eq(&self, other: &GroupElement, group: &Group) -> bool {
+        ct_eq_bytes(
+            &self.to_be_bytes_left_pad(group),
+            &other.to_be_bytes_left_pad(group),
+        )
+    }
+
     /// Returns a reference to group element as BigUint
     pub fn as_biguint(&self) -> &BigUint {
         &self.0
@@ -445,6 +580,26 @@ mod test {
         assert_eq!(a.mul(&a_inv, &field), ScalarField::one());
     }
 
+    #[test]
+    fn test_field_pow_u64() {
+        let (field, _) = get_toy_algebras();
+        let mut csprng = Csprng::new(b"testing field pow_u64");
+
+        let a = FieldElement::from(115_u8, &field);
+        assert_eq!(a.pow_u64(0, &field), ScalarField::one());
+        assert_eq!(a.pow_u64(1, &field), a);
+        assert_eq!(a.pow_u64(2, &field), a.square(&field));
+        assert_eq!(a.pow_u64(23, &field), FieldElement::from(69_u8, &field));
+        assert_eq!(ScalarField::zero().pow_u64(5, &field), ScalarField::zero());
+
+        for _ in 0..10 {
+            let x = field.random_field_elem(&mut csprng);
+            let e = csprng.next_u64();
+            assert_eq!(x.pow_u64(e, &field), x.pow(e, &field));
+            assert_eq!(x.pow_u64(3, &field), x.square(&field).mul(&x, &field));
+        }
+    }
+
     #[test]
     fn test_group_operations() {
         let mut csprng = Csprng::new(b"testing group operations");
@@ -487,6 +642,96 @@ mod test {
         assert!(!h.is_valid(&group));
     }
 
+    #[test]
+    fn test_field_try_batch_invert() {
+        let mut csprng = Csprng::new(b"testing field try_batch_invert");
+        let (field, _) = get_toy_algebras();
+
+        let elems: Vec<_> = (1..=10_u8)
+            .map(|_| {
+                let x = field.random_field_elem(&mut csprng);
+                if x.is_zero() {
+                    ScalarField::one()
+                } else {
+                    x
+                }
+            })
+            .collect();
+        let inverses = FieldElement::try_batch_invert(&elems, &field).unwrap();
+        assert_eq!(inverses.len(), elems.len());
+        for (x, x_inv) in elems.iter().zip(&inverses) {
+            assert_eq!(Some(x_inv.clone()), x.inv(&field));
+            assert_eq!(x.mul(x_inv, &field), ScalarField::one());
+        }
+
+        assert_eq!(FieldElement::try_batch_invert(&[], &field), Ok(vec![]));
+
+        let mut with_zeros = elems;
+        with_zeros[2] = ScalarField::zero();
+        with_zeros[7] = ScalarField::zero();
+        assert_eq!(
+            FieldElement::try_batch_invert(&with_zeros, &field),
+            Err(vec![2, 7])
+        );
+    }
+
+    #[test]
+    fn test_field_eq_u64() {
+        let (field, _) = get_toy_algebras();
+
+        let five = FieldElement::from(5_u8, &field);
+        assert!(five == 5_u64);
+        assert!(5_u64 == five);
+        assert!(five != 6_u64);
+        assert!(6_u64 != five);
+        assert!(ScalarField::zero() == 0_u64);
+
+        // Field elements are reduced on construction, but the integer is not.
+        let wrapped = FieldElement::from(127_u64 + 5, &field);
+        assert!(wrapped == 5_u64);
+        assert!(wrapped != 127_u64 + 5);
+        assert!(FieldElement::from(127_u64, &field) == 0_u64);
+    }
+
+    #[test]
+    fn test_group_ct_eq() {
+        let mut csprng = Csprng::new(b"testing group ct_eq");
+        let (_, group) = get_toy_algebras();
+
+        let elems: Vec<_> = (0..10)
+            .map(|_| group.random_group_elem(&mut csprng))
+            .chain([Group::one(), group.generator()])
+            .collect();
+        for a in &elems {
+            for b in &elems {
+                assert_eq!(a.ct_eq(b, &group), a == b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_group_batch_exp() {
+        let mut csprng = Csprng::new(b"testing group batch_exp");
+        let (field, group) = get_toy_algebras();
+
+        let g = group.generator();
+        let h = group.random_group_elem(&mut csprng);
+
+        let mut exponents: Vec<_> = (0..20)
+            .map(|_| field.random_field_elem(&mut csprng))
+            .collect();
+        exponents.push(ScalarField::zero());
+        exponents.push(ScalarField::one());
+        exponents.push(FieldElement::from(126_u8, &field));
+
+        for base in [g, h] {
+            let expected: Vec<_> = exponents.iter().map(|x| base.exp(x, &group)).collect();
+            assert_eq!(base.batch_exp(&exponents, &group), expected);
+        }
+
+        assert!(group.generator().batch_exp(&[], &group).is_empty());
+    }
+
     #[test]
     fn test_field_group_validity() {
         let mut csprng = Csprng::new(b"testing field/group validity");
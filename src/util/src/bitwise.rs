@@ -4,12 +4,33 @@ pub fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
     a.iter().zip(b).map(|(x, y)| x ^ y).collect()
 }
 
+/// Compares two byte slices for equality without short-circuiting on the first differing byte.
+///
+/// Slices of unequal length are never equal. The length itself is not considered secret.
+pub fn ct_eq_bytes(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    std::hint::black_box(diff) == 0
+}
+
 #[cfg(test)]
 mod test {
-    use crate::bitwise::xor;
+    use crate::bitwise::{ct_eq_bytes, xor};
 
     #[test]
     fn test_xor() {
         assert_eq!(xor(&[0xde, 0xad], &[0xbe, 0xef]), [0x60, 0x42])
     }
+
+    #[test]
+    fn test_ct_eq_bytes() {
+        assert!(ct_eq_bytes(&[], &[]));
+        assert!(ct_eq_bytes(&[0xde, 0xad], &[0xde, 0xad]));
+        assert!(!ct_eq_bytes(&[0xde, 0xad], &[0xde, 0xae]));
+        assert!(!ct_eq_bytes(&[0xde, 0xad], &[0xbe, 0xad]));
+        assert!(!ct_eq_bytes(&[0xde, 0xad], &[0xde, 0xad, 0x00]));
+        assert!(!ct_eq_bytes(&[0xde], &[]));
+    }
 }
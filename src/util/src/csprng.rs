@@ -113,6 +113,15 @@ impl Csprng {
         self.next_biguint_impl(bits, false)
     }
 
+    /// Returns a random number chosen uniformly from `0 <= n < 2^bits`.
+    /// Unlike [`Csprng::next_biguint`], `bits` may be `0`, in which case the result is `0`.
+    pub fn random_biguint(&mut self, bits: usize) -> BigUint {
+        match NonZeroUsize::new(bits) {
+            Some(bits) => self.next_biguint(bits),
+            None => BigUint::zero(),
+        }
+    }
+
     /// Returns a random number that requires exactly the specified number of bits to represent.
     /// If `bits == 1`, chosen uniformly `0` or `1`.
     /// else `bits > 1`, chosen uniformly from `2^(bits - 1) <= n < 2^bits`.
@@ -237,6 +246,31 @@ mod test_csprng {
         }
     }
 
+    #[test]
+    fn random_biguint() {
+        let mut csprng = Csprng::new(b"test_csprng::random_biguint");
+
+        assert!(csprng.random_biguint(0).is_zero());
+
+        for bits in 1..100 {
+            let j = csprng.random_biguint(bits);
+            assert!(j.bits() <= bits as u64);
+        }
+
+        // Over many samples, both the lower and the upper half of the range are hit.
+        let bits = 64;
+        let half = BigUint::one() << (bits - 1);
+        let (mut cnt_low, mut cnt_high) = (0, 0);
+        for _ in 0..1000 {
+            if csprng.random_biguint(bits) < half {
+                cnt_low += 1;
+            } else {
+                cnt_high += 1;
+            }
+        }
+        assert!(400 < cnt_low && 400 < cnt_high);
+    }
+
     #[test]
     fn next_biguint_requiring_bits() {
         let mut csprng = Csprng::new(b"test_csprng::next_biguint_requiring_bits");